@@ -0,0 +1,74 @@
+//! Parsing time should grow linearly with the size of the message.
+
+#![allow(missing_docs)]
+
+use git_conventional::Commit;
+
+const LINES: &[usize] = &[1_000, 10_000, 100_000];
+
+fn main() {
+    divan::main();
+}
+
+/// A squash-merge with one huge paragraph.
+#[divan::bench(args = LINES)]
+fn long_body(bencher: divan::Bencher<'_, '_>, lines: usize) {
+    let mut message = "feat: squash everything\n\n".to_owned();
+    for i in 0..lines {
+        message.push_str(&format!("* fix thing number {i} in the parser\n"));
+    }
+    bencher.bench(|| Commit::parse(divan::black_box(&message)).unwrap());
+}
+
+/// Many paragraphs, each starting with something that looks like a token.
+#[divan::bench(args = LINES)]
+fn footer_like_paragraphs(bencher: divan::Bencher<'_, '_>, lines: usize) {
+    let mut message = "feat: squash everything\n\n".to_owned();
+    for i in 0..lines {
+        message.push_str(&format!("Almost-a-footer {i} without a separator\n\n"));
+    }
+    bencher.bench(|| Commit::parse(divan::black_box(&message)).unwrap());
+}
+
+/// One footer whose value continues across every line.
+#[divan::bench(args = LINES)]
+fn long_footer_value(bencher: divan::Bencher<'_, '_>, lines: usize) {
+    let mut message = "feat: squash everything\n\nBREAKING CHANGE: everything\n".to_owned();
+    for i in 0..lines {
+        message.push_str(&format!("  and also change number {i}\n"));
+    }
+    bencher.bench(|| Commit::parse(divan::black_box(&message)).unwrap());
+}
+
+/// Many footers.
+#[divan::bench(args = LINES)]
+fn many_footers(bencher: divan::Bencher<'_, '_>, lines: usize) {
+    let mut message = "feat: squash everything\n\n".to_owned();
+    for i in 0..lines {
+        message.push_str(&format!("Refs #{i}\n"));
+    }
+    bencher.bench(|| Commit::parse(divan::black_box(&message)).unwrap());
+}
+
+/// Multi-byte characters, whose offsets differ in bytes and `char`s.
+#[divan::bench(args = LINES)]
+fn non_ascii_body(bencher: divan::Bencher<'_, '_>, lines: usize) {
+    let mut message = "feat: ✨ squash everything\n\n".to_owned();
+    for i in 0..lines {
+        message.push_str(&format!("* réparer la chose numéro {i} — 修复\n"));
+    }
+    bencher.bench(|| Commit::parse(divan::black_box(&message)).unwrap());
+}
+
+/// Many footers, iterated without collecting them.
+#[divan::bench(args = LINES)]
+fn many_footers_ref(bencher: divan::Bencher<'_, '_>, lines: usize) {
+    let mut message = "feat: squash everything\n\n".to_owned();
+    for i in 0..lines {
+        message.push_str(&format!("Refs #{i}\n"));
+    }
+    bencher.bench(|| {
+        let commit = git_conventional::CommitRef::parse(divan::black_box(&message)).unwrap();
+        commit.footers().count()
+    });
+}
@@ -0,0 +1,253 @@
+//! The questions asked to write a commit message.
+
+use std::io::{self, BufRead, Write};
+
+use git_conventional::{Commit, Completer, ReferenceExtractor, TypeRegistry};
+
+use crate::prompt::Prompt;
+
+/// How many scopes used before to suggest.
+const SCOPE_SUGGESTIONS: usize = 5;
+
+/// Writes a commit message from answers to questions about the change.
+#[derive(Clone, Debug)]
+pub(crate) struct Composer {
+    registry: TypeRegistry,
+    completer: Completer,
+    references: ReferenceExtractor,
+}
+
+impl Composer {
+    pub(crate) fn new(registry: TypeRegistry) -> Self {
+        Self {
+            registry,
+            completer: Completer::new(),
+            references: ReferenceExtractor::new().any_jira_project(),
+        }
+    }
+
+    /// Suggest the scopes of earlier commits.
+    pub(crate) fn completer(mut self, completer: Completer) -> Self {
+        self.completer = completer;
+        self
+    }
+
+    /// Ask about the change, checking each answer as it is given.
+    pub(crate) fn compose<R: BufRead, W: Write>(
+        &self,
+        prompt: &mut Prompt<R, W>,
+    ) -> io::Result<String> {
+        prompt.say("Select the type of change:")?;
+        let types: Vec<_> = self.registry.iter().collect();
+        let width = types.iter().map(|t| t.name().len()).max().unwrap_or(0);
+        for (i, info) in types.iter().enumerate() {
+            let description = info.description();
+            prompt
+                .say(format!("{:>3}. {:width$}  {description}", i + 1, info.name()).trim_end())?;
+        }
+        let ty = prompt.ask("Type:", |answer| {
+            let info = match answer.parse::<usize>() {
+                Ok(i) => i.checked_sub(1).and_then(|i| types.get(i).copied()),
+                Err(_) => self.registry.get(answer),
+            };
+            let info = info.ok_or_else(|| {
+                format!("unknown type `{answer}`, enter a number or name from the list")
+            })?;
+            check(&format!("{}: x", info.name()))?;
+            Ok(info.name().to_owned())
+        })?;
+
+        let scopes: Vec<_> = self
+            .completer
+            .complete(&format!("{ty}("))
+            .into_iter()
+            .take(SCOPE_SUGGESTIONS)
+            .map(|completion| completion.text().to_owned())
+            .collect();
+        let question = if scopes.is_empty() {
+            "Scope (empty for none):".to_owned()
+        } else {
+            format!("Scope, like {} (empty for none):", scopes.join(", "))
+        };
+        let scope = prompt.ask(&question, |answer| {
+            if answer.is_empty() {
+                return Ok(String::new());
+            }
+            let summary = format!("{ty}({answer}): x");
+            let scope = Commit::parse(&summary)
+                .ok()
+                .and_then(|commit| commit.scope());
+            if scope.map(|s| s.as_str()) != Some(answer) {
+                return Err(format!("invalid scope `{answer}`"));
+            }
+            Ok(format!("({answer})"))
+        })?;
+
+        let breaking = prompt.ask("Is this a breaking change? [y/N]", yes_no)?;
+        let breaking = if breaking {
+            Some(prompt.ask("Describe the breaking change:", |answer| {
+                if answer.is_empty() {
+                    return Err("a description is required".to_owned());
+                }
+                check(&format!("{ty}!: x\n\nBREAKING CHANGE: {answer}"))?;
+                Ok(answer.to_owned())
+            })?)
+        } else {
+            None
+        };
+        let header = format!("{ty}{scope}{}", if breaking.is_some() { "!" } else { "" });
+
+        let description = prompt.ask("Short description:", |answer| {
+            if answer.is_empty() {
+                return Err("a description is required".to_owned());
+            }
+            let summary = format!("{header}: {answer}");
+            let commit = check(&summary)?;
+            if commit.description() != answer {
+                return Err(format!("invalid description `{answer}`"));
+            }
+            Ok(summary)
+        })?;
+
+        let body =
+            prompt.ask_lines("Longer description (end with two empty lines):", |answer| {
+                if answer.is_empty() {
+                    return Ok(None);
+                }
+                let message = format!("{description}\n\n{answer}");
+                let commit = check(&message)?;
+                if let Some(footer) = commit.footers().first() {
+                    return Err(format!(
+                        "the last paragraph would be read as a `{}` footer",
+                        footer.token()
+                    ));
+                }
+                Ok(Some(answer.to_owned()))
+            })?;
+
+        let references = prompt.ask(
+            "Issues this references, like #12 (empty for none):",
+            |answer| {
+                answer
+                    .split([',', ' '])
+                    .filter(|reference| !reference.is_empty())
+                    .map(|reference| self.reference(reference))
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        let mut message = description;
+        if let Some(body) = body {
+            message.push_str("\n\n");
+            message.push_str(&body);
+        }
+        let mut footers = Vec::new();
+        if let Some(breaking) = breaking {
+            footers.push(format!("BREAKING CHANGE: {breaking}"));
+        }
+        for reference in references {
+            footers.push(format!("Refs: {reference}"));
+        }
+        if !footers.is_empty() {
+            message.push_str("\n\n");
+            message.push_str(&footers.join("\n"));
+        }
+        message.push('\n');
+        check(&message).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(message)
+    }
+
+    /// Check that `reference` is a single issue reference, like `#12` or `owner/repo#34`.
+    fn reference(&self, reference: &str) -> Result<String, String> {
+        let message = format!("x: x\n\nRefs: {reference}");
+        let commit = check(&message)?;
+        match self.references.extract(&commit).as_slice() {
+            [found] if found.as_str() == reference => Ok(reference.to_owned()),
+            _ => Err(format!("`{reference}` is not an issue reference")),
+        }
+    }
+}
+
+/// Parse a message, describing why it is invalid.
+fn check(message: &str) -> Result<Commit<'_>, String> {
+    Commit::parse(message).map_err(|err| err.to_string())
+}
+
+fn yes_no(answer: &str) -> Result<bool, String> {
+    match answer.to_ascii_lowercase().as_str() {
+        "" | "n" | "no" => Ok(false),
+        "y" | "yes" => Ok(true),
+        _ => Err("answer `y` or `n`".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn compose(composer: &Composer, script: &str) -> (io::Result<String>, String) {
+        let mut output = Vec::new();
+        let message = composer.compose(&mut Prompt::new(script.as_bytes(), &mut output));
+        (message, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_minimal() {
+        let composer = Composer::new(TypeRegistry::conventional());
+        let (message, output) = compose(&composer, "fix\n\n\nhandle empty input\n\n\n");
+        assert_eq!(message.unwrap(), "fix: handle empty input\n");
+        assert!(output.starts_with("Select the type of change:\n  1. feat      A new feature\n"));
+        assert!(output.contains(" 11. chore     Other changes"));
+        assert!(output.contains("Scope (empty for none): "));
+    }
+
+    #[test]
+    fn test_full() {
+        let composer = Composer::new(TypeRegistry::conventional());
+        let script = "1\napi\ny\nremove v1 endpoints\nadd v2 endpoints\n\
+                      They are faster.\n\nAnd simpler.\n\n\n#12, owner/repo#34\n";
+        let (message, _) = compose(&composer, script);
+        assert_eq!(
+            message.unwrap(),
+            "feat(api)!: add v2 endpoints\n\nThey are faster.\n\nAnd simpler.\n\n\
+             BREAKING CHANGE: remove v1 endpoints\nRefs: #12\nRefs: owner/repo#34\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_answers() {
+        let composer = Composer::new(TypeRegistry::conventional());
+        let script = "wip\n99\nbugfix\nap)i\napi\nmaybe\nn\n\nadd thing\n\
+                      Some detail\n\nNote: read as footer\n\n\nSome detail\n\n\n12\n#12\n";
+        let (message, output) = compose(&composer, script);
+        assert_eq!(
+            message.unwrap(),
+            "fix(api): add thing\n\nSome detail\n\nRefs: #12\n"
+        );
+        assert!(output.contains("unknown type `wip`"));
+        assert!(output.contains("unknown type `99`"));
+        assert!(output.contains("invalid scope `ap)i`"));
+        assert!(output.contains("answer `y` or `n`"));
+        assert!(output.contains("a description is required"));
+        assert!(output.contains("would be read as a `Note` footer"));
+        assert!(output.contains("`12` is not an issue reference"));
+    }
+
+    #[test]
+    fn test_suggested_scopes() {
+        let history = ["fix(api): a", "fix(ui): b", "fix(ui): c", "feat(cli): d"];
+        let commits: Vec<_> = history.iter().map(|m| Commit::parse(m).unwrap()).collect();
+        let composer =
+            Composer::new(TypeRegistry::conventional()).completer(commits.iter().collect());
+        let (message, output) = compose(&composer, "fix\nui\n\nx\n\n\n");
+        assert_eq!(message.unwrap(), "fix(ui): x\n");
+        assert!(output.contains("Scope, like ui, api, cli (empty for none): "));
+    }
+
+    #[test]
+    fn test_eof() {
+        let composer = Composer::new(TypeRegistry::conventional());
+        let (message, _) = compose(&composer, "fix\n");
+        assert_eq!(message.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,92 @@
+//! Interactively write [Conventional Commit] messages.
+//!
+//! Asks for the type, scope, breaking changes, description, body, and issue references of a
+//! change, checking each answer as it is given, then writes the message to a file or stdout.
+//! Questions are written to stderr and answers read from stdin, so they can be scripted.
+//!
+//! [Conventional Commit]: https://www.conventionalcommits.org
+
+mod compose;
+mod prompt;
+
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
+
+use clap::Parser;
+use git_conventional::{Completer, LogReader, TypeInfo, TypeRegistry};
+
+use crate::compose::Composer;
+use crate::prompt::Prompt;
+
+/// How many earlier commits to suggest scopes from.
+const HISTORY: usize = 1000;
+
+#[derive(Debug, Parser)]
+#[command(about, version)]
+struct Args {
+    /// Write the message here, like `.git/COMMIT_EDITMSG`, instead of stdout
+    file: Option<PathBuf>,
+
+    /// Also offer this type, in addition to the conventional ones
+    #[arg(long = "type", value_name = "NAME")]
+    types: Vec<String>,
+
+    /// Don't suggest scopes used by earlier commits, from `git log`
+    #[arg(long)]
+    no_history: bool,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> io::Result<()> {
+    let mut registry = TypeRegistry::conventional();
+    for ty in &args.types {
+        registry.insert(TypeInfo::new(ty.as_str()));
+    }
+    let mut composer = Composer::new(registry);
+    if !args.no_history {
+        composer = composer.completer(history());
+    }
+
+    let mut prompt = Prompt::new(io::stdin().lock(), io::stderr());
+    let message = composer.compose(&mut prompt)?;
+    match &args.file {
+        Some(file) => std::fs::write(file, message),
+        None => {
+            print!("{message}");
+            Ok(())
+        }
+    }
+}
+
+/// Learn from the current repository's recent commits, if there is one.
+fn history() -> Completer {
+    let mut completer = Completer::new();
+    let output = Command::new("git")
+        .arg("log")
+        .arg(format!("--format={}", LogReader::<&[u8]>::FORMAT))
+        .arg(format!("--max-count={HISTORY}"))
+        .output();
+    let Ok(output) = output else {
+        return completer;
+    };
+    if !output.status.success() {
+        return completer;
+    }
+    for entry in LogReader::new(output.stdout.as_slice()).flatten() {
+        if let Ok(commit) = entry.commit() {
+            completer.add(&commit);
+        }
+    }
+    completer
+}
@@ -0,0 +1,108 @@
+//! Asking questions over a reader and writer, like a terminal or a script.
+
+use std::io::{self, BufRead, Write};
+
+/// Asks questions, repeating them until the answer is accepted.
+#[derive(Debug)]
+pub(crate) struct Prompt<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Prompt<R, W> {
+    pub(crate) fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// Write a line, like a menu of choices, before a question.
+    pub(crate) fn say(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.output, "{line}")
+    }
+
+    /// Ask until `check` accepts the trimmed answer, explaining why it was not.
+    pub(crate) fn ask<T>(
+        &mut self,
+        question: &str,
+        mut check: impl FnMut(&str) -> Result<T, String>,
+    ) -> io::Result<T> {
+        loop {
+            write!(self.output, "{question} ")?;
+            self.output.flush()?;
+            let answer = self.read_line()?;
+            match check(answer.trim()) {
+                Ok(answer) => return Ok(answer),
+                Err(err) => writeln!(self.output, "  {err}")?,
+            }
+        }
+    }
+
+    /// Ask for paragraphs until `check` accepts them.
+    ///
+    /// The answer ends at an empty first line or at two empty lines in a row.  Lines are joined
+    /// without trailing whitespace or the final empty lines.
+    pub(crate) fn ask_lines<T>(
+        &mut self,
+        question: &str,
+        mut check: impl FnMut(&str) -> Result<T, String>,
+    ) -> io::Result<T> {
+        loop {
+            writeln!(self.output, "{question}")?;
+            self.output.flush()?;
+            let mut lines = Vec::new();
+            loop {
+                let line = self.read_line()?;
+                let line = line.trim_end();
+                if line.is_empty() && lines.last().is_none_or(String::is_empty) {
+                    lines.pop();
+                    break;
+                }
+                lines.push(line.to_owned());
+            }
+            match check(&lines.join("\n")) {
+                Ok(answer) => return Ok(answer),
+                Err(err) => writeln!(self.output, "  {err}")?,
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input ended before the message was complete",
+            ));
+        }
+        Ok(line)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ask() {
+        let mut output = Vec::new();
+        let mut prompt = Prompt::new(&b"x\n 12 \n"[..], &mut output);
+        let answer = prompt.ask("Number?", |a| a.parse::<u32>().map_err(|e| e.to_string()));
+        assert_eq!(answer.unwrap(), 12);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Number?   invalid digit found in string\nNumber? "
+        );
+    }
+
+    #[test]
+    fn test_ask_lines() {
+        let script = "one  \r\ntwo\n\nthree\n\n\n\nfour\n";
+        let mut prompt = Prompt::new(script.as_bytes(), io::sink());
+        let answer = prompt.ask_lines("Lines:", |a| Ok::<_, String>(a.to_owned()));
+        assert_eq!(answer.unwrap(), "one\ntwo\n\nthree");
+        let answer = prompt.ask_lines("Lines:", |a| Ok::<_, String>(a.to_owned()));
+        assert_eq!(answer.unwrap(), "");
+
+        let err = prompt.ask_lines("Lines:", |a| Ok::<_, String>(a.to_owned()));
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
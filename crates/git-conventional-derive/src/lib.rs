@@ -0,0 +1,251 @@
+//! Derive macros for [git-conventional](https://docs.rs/git-conventional).
+//!
+//! Enable the `derive` feature of `git-conventional` rather than depending on this crate directly.
+
+#![warn(missing_docs)]
+#![warn(clippy::print_stderr)]
+#![warn(clippy::print_stdout)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input, spanned::Spanned};
+
+/// Implement `ConventionalType`, `FromStr`, `TryFrom<Type>`, and `Display` for an enum of commit
+/// types.
+///
+/// Each variant must be a unit variant.  By default, a variant is named by its lowercased
+/// identifier; this and its metadata can be changed with `#[conventional(...)]`:
+/// - `rename = "..."`: the canonical name of the type
+/// - `alias = "..."`: another accepted name, may be repeated
+/// - `bump = "none" | "patch" | "minor" | "major"`: the semver impact
+///
+/// The variant's doc comment is used as its description.
+#[proc_macro_derive(ConventionalType, attributes(conventional))]
+pub fn derive_conventional_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Variant {
+    ident: syn::Ident,
+    name: String,
+    aliases: Vec<String>,
+    bump: TokenStream2,
+    description: String,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`ConventionalType` can only be derived for enums",
+        ));
+    };
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(
+            input.span(),
+            "`ConventionalType` requires at least one variant",
+        ));
+    }
+    let variants = data
+        .variants
+        .iter()
+        .map(parse_variant)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let from_type = variants.iter().map(|v| {
+        let ident = &v.ident;
+        let names = std::iter::once(&v.name).chain(&v.aliases);
+        quote! {
+            if #(ty == #names)||* {
+                return ::core::option::Option::Some(Self::#ident);
+            }
+        }
+    });
+    let idents: Vec<_> = variants.iter().map(|v| &v.ident).collect();
+    let names = variants.iter().map(|v| &v.name);
+    let aliases = variants.iter().map(|v| {
+        let aliases = &v.aliases;
+        quote! { &[#(#aliases),*] }
+    });
+    let bumps = variants.iter().map(|v| &v.bump);
+    let descriptions = variants.iter().map(|v| &v.description);
+
+    Ok(quote! {
+        impl #impl_generics ::git_conventional::ConventionalType for #ident #ty_generics #where_clause {
+            fn from_type(ty: ::git_conventional::Type<'_>) -> ::core::option::Option<Self> {
+                #(#from_type)*
+                ::core::option::Option::None
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #names,)*
+                }
+            }
+
+            fn aliases(&self) -> &'static [&'static str] {
+                match self {
+                    #(Self::#idents => #aliases,)*
+                }
+            }
+
+            fn bump(&self) -> ::git_conventional::Bump {
+                match self {
+                    #(Self::#idents => #bumps,)*
+                }
+            }
+
+            fn description(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #descriptions,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::git_conventional::Error;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                <Self as ::git_conventional::ConventionalType>::parse_type(s)
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<::git_conventional::Type<'_>> for #ident #ty_generics #where_clause {
+            type Error = ::git_conventional::Error;
+
+            fn try_from(ty: ::git_conventional::Type<'_>) -> ::core::result::Result<Self, Self::Error> {
+                <Self as ::git_conventional::ConventionalType>::parse_type(ty.as_str())
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(<Self as ::git_conventional::ConventionalType>::name(self))
+            }
+        }
+    })
+}
+
+fn parse_variant(variant: &syn::Variant) -> syn::Result<Variant> {
+    if !matches!(variant.fields, Fields::Unit) {
+        return Err(syn::Error::new(
+            variant.fields.span(),
+            "`ConventionalType` variants cannot have fields",
+        ));
+    }
+
+    let mut name = variant.ident.to_string().to_lowercase();
+    let mut aliases = Vec::new();
+    let mut bump = quote! { ::git_conventional::Bump::None };
+    let mut description = Vec::new();
+    for attr in &variant.attrs {
+        if attr.path().is_ident("doc") {
+            if let syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) = &attr.meta
+            {
+                let doc = doc.value();
+                let doc = doc.trim();
+                if !doc.is_empty() {
+                    description.push(doc.to_owned());
+                }
+            }
+        } else if attr.path().is_ident("conventional") {
+            attr.parse_nested_meta(|meta| {
+                let value: LitStr = meta.value()?.parse()?;
+                if meta.path.is_ident("rename") {
+                    name = value.value();
+                } else if meta.path.is_ident("alias") {
+                    aliases.push(value.value());
+                } else if meta.path.is_ident("bump") {
+                    bump = match value.value().as_str() {
+                        "none" => quote! { ::git_conventional::Bump::None },
+                        "patch" => quote! { ::git_conventional::Bump::Patch },
+                        "minor" => quote! { ::git_conventional::Bump::Minor },
+                        "major" => quote! { ::git_conventional::Bump::Major },
+                        _ => {
+                            return Err(syn::Error::new(
+                                value.span(),
+                                "expected `none`, `patch`, `minor`, or `major`",
+                            ));
+                        }
+                    };
+                } else {
+                    return Err(meta.error("expected `rename`, `alias`, or `bump`"));
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(Variant {
+        ident: variant.ident.clone(),
+        name,
+        aliases,
+        bump,
+        description: description.join(" "),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(input: DeriveInput) -> String {
+        expand(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_expand() {
+        let expanded = expand(&syn::parse_quote! {
+            enum Kind {
+                #[conventional(rename = "feature")]
+                Feat,
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(expanded.contains("impl :: git_conventional :: ConventionalType for Kind"));
+        assert!(expanded.contains("\"feature\""));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            error(syn::parse_quote! { struct Kind; }),
+            "`ConventionalType` can only be derived for enums"
+        );
+        assert_eq!(
+            error(syn::parse_quote! { enum Kind {} }),
+            "`ConventionalType` requires at least one variant"
+        );
+        assert_eq!(
+            error(syn::parse_quote! { enum Kind { Feat(u8) } }),
+            "`ConventionalType` variants cannot have fields"
+        );
+        assert_eq!(
+            error(syn::parse_quote! { enum Kind { #[conventional(bump = "huge")] Feat } }),
+            "expected `none`, `patch`, `minor`, or `major`"
+        );
+        assert_eq!(
+            error(syn::parse_quote! { enum Kind { #[conventional(section = "x")] Feat } }),
+            "expected `rename`, `alias`, or `bump`"
+        );
+        assert_eq!(
+            error(syn::parse_quote! { enum Kind { #[conventional(rename = 1)] Feat } }),
+            "expected string literal"
+        );
+    }
+}
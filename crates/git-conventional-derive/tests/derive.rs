@@ -0,0 +1,61 @@
+use git_conventional::{Bump, Commit, ConventionalType, ErrorKind, Type};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ConventionalType)]
+enum Kind {
+    /// A new feature
+    #[conventional(alias = "feature", alias = "enhancement", bump = "minor")]
+    Feat,
+    /// A bug fix,
+    /// across lines
+    #[conventional(bump = "patch")]
+    Fix,
+    #[conventional(rename = "ops-change")]
+    Ops,
+}
+
+#[test]
+fn from_str() {
+    assert_eq!("feat".parse::<Kind>().unwrap(), Kind::Feat);
+    assert_eq!("Enhancement".parse::<Kind>().unwrap(), Kind::Feat);
+    assert_eq!("FIX".parse::<Kind>().unwrap(), Kind::Fix);
+    assert_eq!("ops-change".parse::<Kind>().unwrap(), Kind::Ops);
+
+    let err = "ops".parse::<Kind>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownType);
+    assert_eq!(err.to_string(), "Unknown commit type: `ops`");
+    assert!("".parse::<Kind>().is_err());
+}
+
+#[test]
+fn try_from_type() {
+    assert_eq!(Kind::try_from(Type::FEAT).unwrap(), Kind::Feat);
+    let err = Kind::try_from(Type::DOCS).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownType);
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(Kind::Feat.name(), "feat");
+    assert_eq!(Kind::Ops.name(), "ops-change");
+    assert_eq!(Kind::Ops.to_string(), "ops-change");
+    assert_eq!(Kind::Feat.aliases(), ["feature", "enhancement"]);
+    assert!(Kind::Fix.aliases().is_empty());
+    assert_eq!(Kind::Feat.bump(), Bump::Minor);
+    assert_eq!(Kind::Ops.bump(), Bump::None);
+    assert_eq!(Kind::Fix.description(), "A bug fix, across lines");
+    assert_eq!(Kind::Ops.description(), "");
+
+    let info = Kind::Feat.type_info();
+    assert_eq!(info.description(), "A new feature");
+    assert_eq!(info.bump(), Bump::Minor);
+}
+
+#[test]
+fn parse_as() {
+    let commit = Commit::parse_as::<Kind>("feature(ui): add dark mode").unwrap();
+    assert_eq!(*commit.type_(), Kind::Feat);
+
+    let err = Commit::parse_as::<Kind>("docs: add guide").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownType);
+    assert_eq!(err.offset(), Some(0));
+}
@@ -0,0 +1,571 @@
+//! Language features over a single [`Message`].
+
+use std::collections::HashMap;
+
+use git_conventional::{ErrorKind, Fix, Highlighter, IncrementalParser, TokenKind, TypeInfo};
+use lsp_types::{
+    CodeAction, CodeActionKind, CompletionItem, CompletionItemKind, CompletionTextEdit, Diagnostic,
+    DiagnosticSeverity, Documentation, Hover, HoverContents, MarkupContent, MarkupKind,
+    NumberOrString, Position, SemanticToken, SemanticTokenType, TextEdit, Uri, WorkspaceEdit,
+};
+
+use crate::config::Config;
+use crate::message::Message;
+
+/// The `source` of published diagnostics.
+pub(crate) const SOURCE: &str = "git-conventional";
+
+/// The semantic token types, as indexed by [`semantic_tokens`].
+pub(crate) const TOKEN_TYPES: [SemanticTokenType; 5] = [
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::NUMBER,
+];
+
+/// Footer tokens to complete, with their documentation.
+pub(crate) const FOOTER_TOKENS: &[(&str, &str)] = &[
+    (
+        "BREAKING CHANGE",
+        "Describes a breaking change, requiring a major version bump",
+    ),
+    ("Refs", "Issues related to this commit, like `#12`"),
+    ("Closes", "Issues resolved by this commit, like `#12`"),
+    ("Fixes", "Bugs fixed by this commit, like `#12`"),
+    (
+        "Co-authored-by",
+        "Another author of this commit, as `Name <email>`",
+    ),
+    (
+        "Signed-off-by",
+        "Certifies the Developer Certificate of Origin, as `Name <email>`",
+    ),
+    (
+        "Reviewed-by",
+        "A reviewer of this commit, as `Name <email>`",
+    ),
+];
+
+/// Problems with the message, in the order they appear.
+///
+/// Errors in the summary and in the body or footers are reported independently.  Empty
+/// messages are left alone, as git aborts the commit rather than recording them.
+pub(crate) fn diagnostics(message: &Message, config: &Config) -> Vec<Diagnostic> {
+    if message.cleaned().trim().is_empty() {
+        return Vec::new();
+    }
+
+    let parser = IncrementalParser::new(message.cleaned());
+    let mut diagnostics = Vec::new();
+    for err in [parser.summary_error(), parser.trailer_error()]
+        .into_iter()
+        .flatten()
+    {
+        let start = message.original(err.offset());
+        let end = message.line(start).end.max(start);
+        diagnostics.push(diagnostic(
+            message.range(start..end),
+            DiagnosticSeverity::ERROR,
+            err.kind(),
+            err.kind().to_string(),
+        ));
+    }
+
+    if let (Some(span), Some(ty)) = (parser.type_span(), parser.type_()) {
+        if !config.registry.contains(&ty) {
+            diagnostics.push(diagnostic(
+                message.range(message.original_range(span)),
+                DiagnosticSeverity::WARNING,
+                ErrorKind::UnknownType,
+                format!("{}: `{ty}`", ErrorKind::UnknownType),
+            ));
+        }
+    }
+
+    diagnostics.sort_by_key(|d| d.range.start);
+    diagnostics
+}
+
+fn diagnostic(
+    range: lsp_types::Range,
+    severity: DiagnosticSeverity,
+    kind: ErrorKind,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(kind.id().to_owned())),
+        source: Some(SOURCE.to_owned()),
+        message,
+        ..Diagnostic::default()
+    }
+}
+
+/// Completions for the type or scope of the summary, or the token of a footer.
+pub(crate) fn completions(
+    message: &Message,
+    config: &Config,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let offset = message.offset(position);
+    let Some(cursor) = message.cleaned_offset(offset) else {
+        return Vec::new();
+    };
+    let cleaned = message.cleaned();
+    let line_start = cleaned[..cursor].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let prefix = &cleaned[line_start..cursor];
+
+    if line_start == 0 {
+        if !prefix.contains(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ':' | '!')) {
+            let range = message.range(message.original(0)..offset);
+            return config
+                .completer
+                .complete("")
+                .into_iter()
+                .enumerate()
+                .map(|(i, completion)| {
+                    let info = config.registry.get(completion.text());
+                    CompletionItem {
+                        label: completion.text().to_owned(),
+                        kind: Some(CompletionItemKind::KEYWORD),
+                        detail: info.map(|info| info.description().to_owned()),
+                        documentation: info.map(|info| markdown(type_docs(info))),
+                        sort_text: Some(format!("{i:04}")),
+                        text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+                            range,
+                            completion.text().to_owned(),
+                        ))),
+                        ..CompletionItem::default()
+                    }
+                })
+                .collect();
+        }
+
+        if let Some((_, scope)) = prefix.split_once('(') {
+            if scope.contains([')', ':']) {
+                return Vec::new();
+            }
+            let range = message.range(offset - scope.len()..offset);
+            // Scopes used before come first, then configured ones
+            let mut scopes: Vec<_> = config
+                .completer
+                .complete(&cleaned[..cursor - scope.len()])
+                .into_iter()
+                .map(|completion| completion.text().to_owned())
+                .collect();
+            for scope in &config.scopes {
+                if !scopes.iter().any(|s| s.eq_ignore_ascii_case(scope)) {
+                    scopes.push(scope.clone());
+                }
+            }
+            return scopes
+                .into_iter()
+                .enumerate()
+                .map(|(i, scope)| CompletionItem {
+                    label: scope.clone(),
+                    kind: Some(CompletionItemKind::MODULE),
+                    sort_text: Some(format!("{i:04}")),
+                    text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(range, scope))),
+                    ..CompletionItem::default()
+                })
+                .collect();
+        }
+        return Vec::new();
+    }
+
+    // The completer decides whether the line starts a footer, like the parser
+    let range = message.range(offset - prefix.len()..offset);
+    config
+        .completer
+        .complete(&cleaned[..cursor])
+        .into_iter()
+        .map(|completion| completion.text().to_owned())
+        .enumerate()
+        .map(|(i, token)| CompletionItem {
+            documentation: FOOTER_TOKENS
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(&token))
+                .map(|(_, docs)| markdown((*docs).to_owned())),
+            kind: Some(CompletionItemKind::FIELD),
+            sort_text: Some(format!("{i:04}")),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+                range,
+                format!("{token}: "),
+            ))),
+            label: token,
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// Documentation for the type or footer token under the cursor.
+pub(crate) fn hover(message: &Message, config: &Config, position: Position) -> Option<Hover> {
+    let cursor = message.cleaned_offset(message.offset(position))?;
+    let parser = IncrementalParser::new(message.cleaned());
+
+    let (span, docs) = if let Some(span) = parser.type_span().filter(|s| s.contains(&cursor)) {
+        let info = config.registry.get(&message.cleaned()[span.clone()])?;
+        (span, type_docs(info))
+    } else {
+        let (span, footer) = parser
+            .footer_spans()
+            .iter()
+            .zip(parser.footers())
+            .find(|(span, _)| span.token().contains(&cursor))?;
+        let docs = if footer.breaking() {
+            FOOTER_TOKENS[0].1
+        } else {
+            FOOTER_TOKENS
+                .iter()
+                .find(|(token, _)| footer.token() == *token)?
+                .1
+        };
+        (span.token(), format!("**{}**\n\n{docs}", footer.token()))
+    };
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: docs,
+        }),
+        range: Some(message.range(message.original_range(span))),
+    })
+}
+
+/// The parts of the message to color, encoded relative to each other.
+///
+/// Free-form text is left to the editor, and invalid text to [`diagnostics`].
+pub(crate) fn semantic_tokens(message: &Message) -> Vec<SemanticToken> {
+    let mut previous = Position::new(0, 0);
+    let mut tokens = Vec::new();
+    for token in Highlighter::new().tokens(message.cleaned()) {
+        let token_type = match token.kind() {
+            TokenKind::Type => 0,
+            TokenKind::Scope => 1,
+            TokenKind::Breaking | TokenKind::Punctuation | TokenKind::FooterSeparator => 2,
+            TokenKind::FooterToken => 3,
+            TokenKind::IssueRef => 4,
+            _ => continue,
+        };
+        // These are all within a line
+        let range = message.range(message.original_range(token.range()));
+        let start = range.start;
+        tokens.push(SemanticToken {
+            delta_line: start.line - previous.line,
+            delta_start: if start.line == previous.line {
+                start.character - previous.character
+            } else {
+                start.character
+            },
+            length: range.end.character - start.character,
+            token_type,
+            token_modifiers_bitset: 0,
+        });
+        previous = start;
+    }
+    tokens
+}
+
+fn type_docs(info: &TypeInfo) -> String {
+    let mut docs = format!("**{}**: {}", info.name(), info.description());
+    if !info.aliases().is_empty() {
+        docs.push_str(&format!(
+            "\n\nAlso written as `{}`.",
+            info.aliases().join("`, `")
+        ));
+    }
+    if info.bump() != git_conventional::Bump::None {
+        docs.push_str(&format!("\n\nReleased as a {} version bump.", info.bump()));
+    }
+    match info.section() {
+        Some(section) if !info.is_hidden() => {
+            docs.push_str(&format!("\n\nListed under *{section}* in changelogs."));
+        }
+        _ => docs.push_str("\n\nHidden from changelogs."),
+    }
+    docs
+}
+
+fn markdown(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
+
+/// Quick-fixes for the summary, from [`Fix::suggest`].
+pub(crate) fn code_actions(
+    uri: &Uri,
+    message: &Message,
+    range: lsp_types::Range,
+    diagnostics: Vec<Diagnostic>,
+) -> Vec<CodeAction> {
+    let summary_line = message.position(message.original(0)).line;
+    if !(range.start.line..=range.end.line).contains(&summary_line) {
+        return Vec::new();
+    }
+    let Some(fix) = Fix::suggest(message.cleaned()) else {
+        return Vec::new();
+    };
+
+    // Replace only what changed, leaving comments between the lines alone
+    let (before, after) = (message.cleaned(), fix.message());
+    let prefix = before
+        .bytes()
+        .zip(after.bytes())
+        .take_while(|(b, a)| b == a)
+        .count();
+    let suffix = before[prefix..]
+        .bytes()
+        .rev()
+        .zip(after[prefix..].bytes().rev())
+        .take_while(|(b, a)| b == a)
+        .count();
+    let prefix = floor_char_boundary(before, prefix).min(floor_char_boundary(after, prefix));
+    let suffix = ceil_suffix(before, suffix).min(ceil_suffix(after, suffix));
+    let changed = message.original_range(prefix..before.len() - suffix);
+    let edit = TextEdit::new(
+        message.range(changed),
+        after[prefix..after.len() - suffix].to_owned(),
+    );
+
+    let title = fix
+        .repairs()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    vec![CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(
+            diagnostics
+                .into_iter()
+                .filter(|d| d.source.as_deref() == Some(SOURCE))
+                .collect(),
+        ),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+            ..WorkspaceEdit::default()
+        }),
+        is_preferred: Some(true),
+        ..CodeAction::default()
+    }]
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// The longest suffix of at most `len` bytes starting on a character boundary.
+fn ceil_suffix(text: &str, len: usize) -> usize {
+    let mut start = text.len() - len;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    text.len() - start
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn message(text: &str) -> Message {
+        Message::new(text.to_owned(), '#')
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let config = Config::default();
+        assert_eq!(diagnostics(&message("feat: add thing\n"), &config), []);
+        assert_eq!(diagnostics(&message("\n# Please enter\n"), &config), []);
+
+        let diagnostics = diagnostics(
+            &message("# template\nwip add thing\n\nbody\nRefs: #1\n"),
+            &config,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("missing-type".to_owned()))
+        );
+        assert_eq!(diagnostics[0].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_unknown_type() {
+        let text = "wip(api): add thing\nbody\n";
+        let diagnostics = diagnostics(&message(text), &Config::default());
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .map(|d| d.code.clone().unwrap())
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                NumberOrString::String("unknown-type".to_owned()),
+                NumberOrString::String("invalid-body".to_owned()),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].range,
+            lsp_types::Range::new(Position::new(0, 0), Position::new(0, 3))
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+
+        let config =
+            Config::from_options(Some(serde_json::json!({"types": [{"name": "wip"}]}))).unwrap();
+        assert!(self::diagnostics(&message("wip: add thing"), &config).is_empty());
+    }
+
+    #[test]
+    fn test_complete_types() {
+        let config = Config::default();
+        let items = completions(&message("fe"), &config, Position::new(0, 2));
+        assert_eq!(items[0].label, "feat");
+        assert_eq!(items[0].detail.as_deref(), Some("A new feature"));
+        let Some(CompletionTextEdit::Edit(edit)) = &items[0].text_edit else {
+            panic!("expected an edit");
+        };
+        assert_eq!(edit.range.start, Position::new(0, 0));
+        assert_eq!(edit.range.end, Position::new(0, 2));
+
+        let items = completions(&message("# comment\n"), &config, Position::new(0, 3));
+        assert!(items.is_empty());
+        let items = completions(&message("feat: a"), &config, Position::new(0, 7));
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn test_complete_scopes() {
+        let config = Config::from_options(Some(serde_json::json!({"scopes": ["api", "ui"]})));
+        let config = config.unwrap();
+        let items = completions(&message("fix(a"), &config, Position::new(0, 5));
+        let labels: Vec<_> = items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(labels, ["api", "ui"]);
+        let Some(CompletionTextEdit::Edit(edit)) = &items[0].text_edit else {
+            panic!("expected an edit");
+        };
+        assert_eq!(edit.range.start, Position::new(0, 4));
+    }
+
+    #[test]
+    fn test_complete_history() {
+        let config = Config::from_options(Some(serde_json::json!({
+            "scopes": ["api", "ui"],
+            "history": ["docs(ui): a", "docs(readme): b\n\nTicket: 1", "docs(ui): c", "not conventional"],
+        })));
+        let config = config.unwrap();
+        let items = completions(&message("d"), &config, Position::new(0, 1));
+        assert_eq!(items[0].label, "docs");
+        assert_eq!(
+            items[0].detail.as_deref(),
+            Some("Documentation only changes")
+        );
+
+        let items = completions(&message("fix("), &config, Position::new(0, 4));
+        let labels: Vec<_> = items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(labels, ["ui", "readme", "api"]);
+
+        let items = completions(&message("fix: x\n\nT"), &config, Position::new(2, 1));
+        assert_eq!(items[0].label, "Ticket");
+        assert!(items[0].documentation.is_none());
+        let items = completions(&message("fix: x\n\n\n"), &config, Position::new(2, 0));
+        assert_eq!(items[0].label, "Ticket");
+        assert_eq!(items[1].label, "BREAKING CHANGE");
+    }
+
+    #[test]
+    fn test_complete_footers() {
+        let config = Config::default();
+        let text = "fix: x\n\nBody\n\nRefs: #1\nCo";
+        let items = completions(&message(text), &config, Position::new(5, 2));
+        assert!(items.iter().any(|i| i.label == "Co-authored-by"));
+
+        let text = "fix: x\n\nBody\nmore";
+        let items = completions(&message(text), &config, Position::new(3, 4));
+        assert!(items.is_empty());
+        let items = completions(&message("fix: x\nRe"), &config, Position::new(1, 2));
+        assert!(items.is_empty());
+
+        // Like the parser, `Note:` starts a footer even without a value
+        let text = "fix: x\n\nNote:\nRe";
+        let items = completions(&message(text), &config, Position::new(3, 2));
+        let labels: Vec<_> = items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(labels, ["Refs", "Reviewed-by"]);
+        let text = "fix: x\n\nSee this: #1\nRe";
+        assert!(completions(&message(text), &config, Position::new(3, 2)).is_empty());
+    }
+
+    #[test]
+    fn test_hover() {
+        let config = Config::default();
+        let message = message("feat(api): add\n\nBREAKING CHANGE: gone\n");
+        let hover = hover(&message, &config, Position::new(0, 2)).unwrap();
+        let HoverContents::Markup(contents) = hover.contents else {
+            panic!("expected markdown");
+        };
+        assert!(contents.value.starts_with("**feat**: A new feature"));
+        assert_eq!(
+            hover.range,
+            Some(lsp_types::Range::new(
+                Position::new(0, 0),
+                Position::new(0, 4)
+            ))
+        );
+
+        assert!(self::hover(&message, &config, Position::new(2, 3)).is_some());
+        assert!(self::hover(&message, &config, Position::new(0, 6)).is_none());
+    }
+
+    #[test]
+    fn test_semantic_tokens() {
+        let message = message("# template\nfix(ui): x\n# more\n\nCloses #1\n");
+        let tokens: Vec<_> = semantic_tokens(&message)
+            .into_iter()
+            .map(|t| (t.delta_line, t.delta_start, t.length, t.token_type))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (1, 0, 3, 0),
+                (0, 3, 1, 2),
+                (0, 1, 2, 1),
+                (0, 2, 1, 2),
+                (0, 1, 1, 2),
+                (3, 0, 6, 3),
+                (0, 7, 1, 2),
+                (0, 1, 1, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_actions() {
+        let uri: Uri = "file:///repo/.git/COMMIT_EDITMSG".parse().unwrap();
+        let message = message("# template\nfeat!(api) : add\n# more\nbody\n");
+        let range = lsp_types::Range::new(Position::new(1, 0), Position::new(1, 0));
+        let actions = code_actions(&uri, &message, range, Vec::new());
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].title,
+            "Moved `!` after the scope, Removed whitespace before `:`, \
+             Added a blank line after the summary"
+        );
+
+        let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        let mut text = message.text().to_owned();
+        let range = message.offset(edits[0].range.start)..message.offset(edits[0].range.end);
+        text.replace_range(range, &edits[0].new_text);
+        assert_eq!(text, "# template\nfeat(api)!: add\n\n# more\nbody\n");
+
+        let range = lsp_types::Range::new(Position::new(3, 0), Position::new(3, 0));
+        assert!(code_actions(&uri, &message, range, Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Settings from the client's `initializationOptions`.
+
+use git_conventional::{Commit, Completer, TypeInfo, TypeRegistry};
+use serde::Deserialize;
+
+use crate::analysis;
+
+/// How messages are checked and completed.
+#[derive(Clone, Debug)]
+pub(crate) struct Config {
+    pub(crate) registry: TypeRegistry,
+    pub(crate) scopes: Vec<String>,
+    pub(crate) comment_char: char,
+    pub(crate) completer: Completer,
+}
+
+impl Config {
+    /// Read `initializationOptions`, falling back to the defaults for anything missing.
+    pub(crate) fn from_options(options: Option<serde_json::Value>) -> Result<Self, String> {
+        let options: Options = match options {
+            Some(serde_json::Value::Null) | None => Options::default(),
+            Some(options) => serde_json::from_value(options)
+                .map_err(|err| format!("invalid initializationOptions: {err}"))?,
+        };
+
+        let mut registry = TypeRegistry::conventional();
+        for ty in options.types {
+            let mut info = TypeInfo::new(ty.name);
+            if let Some(description) = ty.description {
+                info = info.with_description(description);
+            }
+            registry.insert(info);
+        }
+        let history: Vec<_> = options
+            .history
+            .iter()
+            .filter_map(|message| Commit::parse(message).ok())
+            .collect();
+        let completer = history.iter().collect::<Completer>();
+        let completer = known(completer, &registry);
+        Ok(Self {
+            registry,
+            scopes: options.scopes,
+            comment_char: options.comment_char.unwrap_or('#'),
+            completer,
+        })
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let registry = TypeRegistry::conventional();
+        Self {
+            completer: known(Completer::new(), &registry),
+            registry,
+            scopes: Vec::new(),
+            comment_char: '#',
+        }
+    }
+}
+
+/// Complete the configured types and well-known footer tokens, even if unused.
+fn known(completer: Completer, registry: &TypeRegistry) -> Completer {
+    completer
+        .known_types(registry)
+        .known_footer_tokens(analysis::FOOTER_TOKENS.iter().map(|(token, _)| *token))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct Options {
+    /// Types accepted in addition to the conventional ones
+    types: Vec<TypeOption>,
+    /// Scopes to complete
+    scopes: Vec<String>,
+    /// Like git's `core.commentChar`
+    comment_char: Option<char>,
+    /// Earlier commit messages, to rank completions by
+    history: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TypeOption {
+    name: String,
+    description: Option<String>,
+}
@@ -0,0 +1,32 @@
+//! A language server for [Conventional Commit] messages, like `COMMIT_EDITMSG`.
+//!
+//! The server communicates over stdio.  It publishes diagnostics for messages that do not
+//! conform to the specification, completes types, scopes, and footer tokens, documents types on
+//! hover, offers quick-fixes for common mistakes in the summary, and provides semantic tokens.
+//!
+//! [Conventional Commit]: https://www.conventionalcommits.org
+
+mod analysis;
+mod config;
+mod message;
+mod server;
+
+use std::process::ExitCode;
+
+use lsp_server::Connection;
+
+fn main() -> ExitCode {
+    let (connection, io_threads) = Connection::stdio();
+    let result = server::run(&connection);
+    drop(connection);
+    match result
+        .map_err(|err| err.to_string())
+        .and_then(|()| io_threads.join().map_err(|err| err.to_string()))
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! A commit message file, like `COMMIT_EDITMSG`, as git will clean it up.
+
+use std::ops::Range;
+
+use lsp_types::Position;
+
+/// The line git ignores everything after, e.g. with `git commit --verbose`.
+const SCISSORS: &str = " ------------------------ >8 ------------------------";
+
+/// A commit message file, with the comment lines git strips removed before parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Message {
+    text: String,
+    cleaned: String,
+    /// The start of each kept line, in `cleaned` and then in `text`
+    lines: Vec<(usize, usize)>,
+}
+
+impl Message {
+    pub(crate) fn new(text: String, comment_char: char) -> Self {
+        let mut cleaned = String::with_capacity(text.len());
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in text.split_inclusive('\n') {
+            if let Some(comment) = line.strip_prefix(comment_char) {
+                if comment.trim_end() == SCISSORS {
+                    break;
+                }
+            } else {
+                lines.push((cleaned.len(), start));
+                cleaned.push_str(line);
+            }
+            start += line.len();
+        }
+        Self {
+            text,
+            cleaned,
+            lines,
+        }
+    }
+
+    /// The file, as the editor sees it.
+    #[cfg(test)]
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The commit message, as git will see it.
+    pub(crate) fn cleaned(&self) -> &str {
+        &self.cleaned
+    }
+
+    /// The offset into the file of an offset into [`Message::cleaned`].
+    ///
+    /// Offsets at the start of a line are placed there, after any comments before it.
+    pub(crate) fn original(&self, offset: usize) -> usize {
+        let line = self.lines.partition_point(|(start, _)| *start <= offset);
+        match line.checked_sub(1).map(|line| self.lines[line]) {
+            Some((start, original)) => original + offset - start,
+            None => offset,
+        }
+    }
+
+    /// A range of the file from a range of [`Message::cleaned`].
+    ///
+    /// Ranges ending at the start of a line end before any comments before it.
+    pub(crate) fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = self.original(range.start);
+        let line = self.lines.partition_point(|(start, _)| *start < range.end);
+        let end = match line.checked_sub(1).map(|line| self.lines[line]) {
+            Some((line_start, original)) => original + range.end - line_start,
+            None => range.end,
+        };
+        start..end.max(start)
+    }
+
+    /// The offset into [`Message::cleaned`] of an offset into the file, if it is not in a
+    /// comment.
+    pub(crate) fn cleaned_offset(&self, offset: usize) -> Option<usize> {
+        let line = self.lines.partition_point(|(_, start)| *start <= offset);
+        let (start, original) = self.lines.get(line.checked_sub(1)?)?;
+        let end = self
+            .lines
+            .get(line)
+            .map(|(end, _)| *end)
+            .unwrap_or(self.cleaned.len());
+        let column = offset - original;
+        let line = &self.cleaned[*start..end];
+        (column <= line.trim_end_matches('\n').len()).then_some(start + column)
+    }
+
+    /// The LSP position, in UTF-16 code units, of an offset into the file.
+    pub(crate) fn position(&self, offset: usize) -> Position {
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Position::new(
+            before.matches('\n').count() as u32,
+            before[line_start..].encode_utf16().count() as u32,
+        )
+    }
+
+    /// The LSP range of a range of the file.
+    pub(crate) fn range(&self, range: Range<usize>) -> lsp_types::Range {
+        lsp_types::Range::new(self.position(range.start), self.position(range.end))
+    }
+
+    /// The offset into the file of an LSP position, clamped to its line.
+    pub(crate) fn offset(&self, position: Position) -> usize {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            match self.text[line_start..].find('\n') {
+                Some(end) => line_start += end + 1,
+                None => return self.text.len(),
+            }
+        }
+        let line = self.text[line_start..].lines().next().unwrap_or_default();
+        let mut units = 0;
+        for (i, c) in line.char_indices() {
+            if units >= position.character as usize {
+                return line_start + i;
+            }
+            units += c.len_utf16();
+        }
+        line_start + line.len()
+    }
+
+    /// The range of the file's line containing `offset`, without its line ending.
+    pub(crate) fn line(&self, offset: usize) -> Range<usize> {
+        let start = self.text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line = self.text[start..].lines().next().unwrap_or_default();
+        start..start + line.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_comments() {
+        let text = "# Please enter the commit message\nfeat: add thing\n# comment\n\nBody\n\
+                    # ------------------------ >8 ------------------------\ndiff --git\n";
+        let message = Message::new(text.to_owned(), '#');
+        assert_eq!(message.cleaned(), "feat: add thing\n\nBody\n");
+
+        let body = message.cleaned().find("Body").unwrap();
+        assert_eq!(&message.text()[message.original(body)..][..4], "Body");
+        assert_eq!(message.cleaned_offset(message.original(body)), Some(body));
+        assert_eq!(message.cleaned_offset(3), None);
+        assert_eq!(
+            message.cleaned_offset(text.find("# comment").unwrap()),
+            None
+        );
+
+        // The summary's line ending stays before the comment after it
+        let summary = message.original_range(0..16);
+        assert_eq!(&message.text()[summary], "feat: add thing\n");
+    }
+
+    #[test]
+    fn test_positions() {
+        let message = Message::new("fix: ✨ 😀 x\r\nbody".to_owned(), '#');
+        let x = message.text().find(" x").unwrap() + 1;
+        assert_eq!(message.position(x), Position::new(0, 10));
+        assert_eq!(message.offset(Position::new(0, 10)), x);
+        assert_eq!(message.offset(Position::new(0, 99)), x + 1);
+        assert_eq!(message.offset(Position::new(1, 2)), x + 5);
+        assert_eq!(message.offset(Position::new(9, 0)), message.text().len());
+        assert_eq!(message.line(x), 0..x + 1);
+    }
+}
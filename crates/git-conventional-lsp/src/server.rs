@@ -0,0 +1,410 @@
+//! The language server, over an [`lsp_server::Connection`].
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use lsp_server::{Connection, ErrorCode, Message as LspMessage, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{
+    CodeActionRequest, Completion, HoverRequest, Request as _, SemanticTokensFullRequest,
+};
+use lsp_types::{
+    CodeActionOrCommand, CodeActionProviderCapability, CompletionOptions, HoverProviderCapability,
+    InitializeParams, PublishDiagnosticsParams, SemanticTokens, SemanticTokensFullOptions,
+    SemanticTokensLegend, SemanticTokensOptions, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerCapabilities, ServerInfo, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Uri,
+};
+
+use crate::analysis;
+use crate::config::Config;
+use crate::message::Message;
+
+/// Serve requests until the client asks to shut down.
+pub(crate) fn run(connection: &Connection) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["(".to_owned()]),
+            ..CompletionOptions::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: SemanticTokensLegend {
+                    token_types: analysis::TOKEN_TYPES.to_vec(),
+                    token_modifiers: Vec::new(),
+                },
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..SemanticTokensOptions::default()
+            },
+        )),
+        ..ServerCapabilities::default()
+    };
+    let (id, params) = connection.initialize_start()?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    let config = match Config::from_options(params.initialization_options) {
+        Ok(config) => config,
+        Err(message) => {
+            let response = Response::new_err(id, ErrorCode::InvalidParams as i32, message);
+            connection.sender.send(response.into())?;
+            return Ok(());
+        }
+    };
+    connection.initialize_finish(
+        id,
+        serde_json::json!({
+            "capabilities": capabilities,
+            "serverInfo": ServerInfo {
+                name: env!("CARGO_PKG_NAME").to_owned(),
+                version: Some(env!("CARGO_PKG_VERSION").to_owned()),
+            },
+        }),
+    )?;
+
+    let mut server = Server {
+        connection,
+        config,
+        documents: BTreeMap::new(),
+    };
+    for message in &connection.receiver {
+        match message {
+            LspMessage::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                server.request(request)?;
+            }
+            LspMessage::Notification(notification) => server.notification(notification)?,
+            LspMessage::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+struct Server<'c> {
+    connection: &'c Connection,
+    config: Config,
+    documents: BTreeMap<Uri, Message>,
+}
+
+impl Server<'_> {
+    fn request(&mut self, request: Request) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            Completion::METHOD => {
+                let params = match extract::<Completion>(request) {
+                    Ok(params) => params,
+                    Err(response) => return self.respond(response),
+                };
+                let position = params.text_document_position;
+                self.documents
+                    .get(&position.text_document.uri)
+                    .map(|message| analysis::completions(message, &self.config, position.position))
+                    .map(serde_json::to_value)
+                    .transpose()?
+            }
+            HoverRequest::METHOD => {
+                let params = match extract::<HoverRequest>(request) {
+                    Ok(params) => params,
+                    Err(response) => return self.respond(response),
+                };
+                let position = params.text_document_position_params;
+                self.documents
+                    .get(&position.text_document.uri)
+                    .and_then(|message| analysis::hover(message, &self.config, position.position))
+                    .map(serde_json::to_value)
+                    .transpose()?
+            }
+            CodeActionRequest::METHOD => {
+                let params = match extract::<CodeActionRequest>(request) {
+                    Ok(params) => params,
+                    Err(response) => return self.respond(response),
+                };
+                let uri = params.text_document.uri;
+                self.documents
+                    .get(&uri)
+                    .map(|message| {
+                        analysis::code_actions(
+                            &uri,
+                            message,
+                            params.range,
+                            params.context.diagnostics,
+                        )
+                        .into_iter()
+                        .map(CodeActionOrCommand::CodeAction)
+                        .collect::<Vec<_>>()
+                    })
+                    .map(serde_json::to_value)
+                    .transpose()?
+            }
+            SemanticTokensFullRequest::METHOD => {
+                let params = match extract::<SemanticTokensFullRequest>(request) {
+                    Ok(params) => params,
+                    Err(response) => return self.respond(response),
+                };
+                self.documents
+                    .get(&params.text_document.uri)
+                    .map(|message| {
+                        SemanticTokensResult::Tokens(SemanticTokens {
+                            result_id: None,
+                            data: analysis::semantic_tokens(message),
+                        })
+                    })
+                    .map(serde_json::to_value)
+                    .transpose()?
+            }
+            method => {
+                return self.respond(Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request `{method}`"),
+                ));
+            }
+        };
+        self.respond(Response::new_ok(
+            id,
+            result.unwrap_or(serde_json::Value::Null),
+        ))
+    }
+
+    fn respond(&self, response: Response) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification
+                    .extract::<lsp_types::DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)?;
+                let document = params.text_document;
+                self.update(document.uri, document.text, Some(document.version))?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = notification.extract::<lsp_types::DidChangeTextDocumentParams>(
+                    DidChangeTextDocument::METHOD,
+                )?;
+                // Only full syncs are advertised, so the last change is the whole document
+                if let Some(change) = params.content_changes.into_iter().next_back() {
+                    let document = params.text_document;
+                    self.update(document.uri, change.text, Some(document.version))?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = notification.extract::<lsp_types::DidCloseTextDocumentParams>(
+                    DidCloseTextDocument::METHOD,
+                )?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.publish(uri, Vec::new(), None)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        uri: Uri,
+        text: String,
+        version: Option<i32>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let message = Message::new(text, self.config.comment_char);
+        let diagnostics = analysis::diagnostics(&message, &self.config);
+        self.documents.insert(uri.clone(), message);
+        self.publish(uri, diagnostics, version)
+    }
+
+    fn publish(
+        &self,
+        uri: Uri,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+        version: Option<i32>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, version);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_owned(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+}
+
+/// The params of `request`, or the error to reply with if they are malformed.
+fn extract<R: lsp_types::request::Request>(request: Request) -> Result<R::Params, Response> {
+    let id = request.id.clone();
+    request
+        .extract(R::METHOD)
+        .map(|(_, params)| params)
+        .map_err(|err| Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use lsp_server::RequestId;
+    use lsp_types::{
+        CompletionResponse, DidOpenTextDocumentParams, Hover, Position, TextDocumentItem,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    struct Client {
+        connection: Connection,
+        next_id: i32,
+    }
+
+    impl Client {
+        fn request<R: lsp_types::request::Request>(&mut self, params: R::Params) -> R::Result {
+            let response = self.send(R::METHOD, params);
+            assert!(response.error.is_none(), "{:?}", response.error);
+            serde_json::from_value(response.result.unwrap()).unwrap()
+        }
+
+        fn send(&mut self, method: &str, params: impl serde::Serialize) -> Response {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), method.to_owned(), params);
+            self.connection.sender.send(request.into()).unwrap();
+            loop {
+                match self.connection.receiver.recv().unwrap() {
+                    LspMessage::Response(response) if response.id == id => return response,
+                    _ => {}
+                }
+            }
+        }
+
+        fn notify<N: lsp_types::notification::Notification>(&self, params: N::Params) {
+            let notification = Notification::new(N::METHOD.to_owned(), params);
+            self.connection.sender.send(notification.into()).unwrap();
+        }
+
+        fn diagnostics(&self) -> PublishDiagnosticsParams {
+            loop {
+                if let LspMessage::Notification(notification) =
+                    self.connection.receiver.recv().unwrap()
+                {
+                    if notification.method == PublishDiagnostics::METHOD {
+                        return serde_json::from_value(notification.params).unwrap();
+                    }
+                }
+            }
+        }
+    }
+
+    fn position(uri: &Uri, line: u32, character: u32) -> lsp_types::TextDocumentPositionParams {
+        lsp_types::TextDocumentPositionParams {
+            text_document: lsp_types::TextDocumentIdentifier::new(uri.clone()),
+            position: Position::new(line, character),
+        }
+    }
+
+    #[test]
+    fn test_session() {
+        let (server, client) = Connection::memory();
+        let server = thread::spawn(move || run(&server).unwrap());
+        let mut client = Client {
+            connection: client,
+            next_id: 0,
+        };
+
+        let init = client.request::<lsp_types::request::Initialize>(
+            serde_json::from_value(json!({
+                "capabilities": {},
+                "initializationOptions": {"scopes": ["api"]},
+            }))
+            .unwrap(),
+        );
+        assert_eq!(init.server_info.unwrap().name, "git-conventional-lsp");
+        client.notify::<lsp_types::notification::Initialized>(lsp_types::InitializedParams {});
+
+        let uri: Uri = "file:///repo/.git/COMMIT_EDITMSG".parse().unwrap();
+        client.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(
+                uri.clone(),
+                "git-commit".to_owned(),
+                1,
+                "fix (api): handle EOF\n# Please enter the commit message\n".to_owned(),
+            ),
+        });
+        let published = client.diagnostics();
+        assert_eq!(published.uri, uri);
+        assert_eq!(published.version, Some(1));
+        assert_eq!(published.diagnostics.len(), 1);
+
+        let actions = client.request::<CodeActionRequest>(lsp_types::CodeActionParams {
+            text_document: lsp_types::TextDocumentIdentifier::new(uri.clone()),
+            range: published.diagnostics[0].range,
+            context: lsp_types::CodeActionContext {
+                diagnostics: published.diagnostics,
+                ..lsp_types::CodeActionContext::default()
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        assert_eq!(actions.unwrap().len(), 1);
+
+        client.notify::<DidChangeTextDocument>(lsp_types::DidChangeTextDocumentParams {
+            text_document: lsp_types::VersionedTextDocumentIdentifier::new(uri.clone(), 2),
+            content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "fix(api): handle EOF\n# Please enter the commit message\n".to_owned(),
+            }],
+        });
+        let published = client.diagnostics();
+        assert_eq!(published.version, Some(2));
+        assert!(published.diagnostics.is_empty());
+
+        let hover = client.request::<HoverRequest>(lsp_types::HoverParams {
+            text_document_position_params: position(&uri, 0, 1),
+            work_done_progress_params: Default::default(),
+        });
+        assert!(matches!(hover, Some(Hover { .. })));
+
+        // Malformed params are rejected without stopping the server
+        let response = client.send(HoverRequest::METHOD, json!({"position": "x"}));
+        assert_eq!(
+            response.error.unwrap().code,
+            ErrorCode::InvalidParams as i32
+        );
+
+        let completions = client.request::<Completion>(lsp_types::CompletionParams {
+            text_document_position: position(&uri, 0, 6),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        });
+        let Some(CompletionResponse::Array(items)) = completions else {
+            panic!("expected completions");
+        };
+        assert_eq!(items[0].label, "api");
+
+        let tokens = client.request::<SemanticTokensFullRequest>(lsp_types::SemanticTokensParams {
+            text_document: lsp_types::TextDocumentIdentifier::new(uri.clone()),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        let Some(SemanticTokensResult::Tokens(tokens)) = tokens else {
+            panic!("expected semantic tokens");
+        };
+        assert_eq!(tokens.data.len(), 5);
+
+        client.notify::<DidCloseTextDocument>(lsp_types::DidCloseTextDocumentParams {
+            text_document: lsp_types::TextDocumentIdentifier::new(uri),
+        });
+        assert!(client.diagnostics().diagnostics.is_empty());
+
+        client.request::<lsp_types::request::Shutdown>(());
+        client.notify::<lsp_types::notification::Exit>(());
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,366 @@
+//! Parsing many commits at once.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{
+    Commit, Error, Footer, FooterSeparator, FooterToken, Gitmoji, ParseOptions, Scope, Type, span,
+};
+
+#[cfg(feature = "rayon")]
+impl<'a> Commit<'a> {
+    /// Parse `messages` in parallel, returning each result in the order of `messages`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use git_conventional::{Commit, ParseOptions};
+    ///
+    /// let messages = ["feat: add thing", "Update readme", "fix: handle EOF"];
+    /// let commits = Commit::parse_batch(&messages, &ParseOptions::new());
+    /// assert_eq!(commits[0].as_ref().unwrap().description(), "add thing");
+    /// assert!(commits[1].is_err());
+    /// assert_eq!(commits[2].as_ref().unwrap().description(), "handle EOF");
+    /// ```
+    pub fn parse_batch<S>(messages: &'a [S], options: &ParseOptions) -> Vec<Result<Self, Error>>
+    where
+        S: AsRef<str> + Sync,
+    {
+        use rayon::prelude::*;
+
+        messages
+            .par_iter()
+            .map(|message| Self::parse_with(message.as_ref(), options))
+            .collect()
+    }
+}
+
+/// Owned commits, parsed in bulk.
+///
+/// All messages share one buffer and all footers share another, so a parsed commit costs no
+/// allocations of its own.  With the `rayon` feature, messages added together are parsed in
+/// parallel.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{CommitStore, ErrorKind, Type};
+///
+/// let messages = vec![
+///     "feat: add thing\n\nCloses #1".to_owned(),
+///     "Update readme".to_owned(),
+/// ];
+/// let store: CommitStore = messages.into_iter().collect();
+/// assert_eq!(store.len(), 2);
+///
+/// let commit = store.get(0).unwrap().unwrap();
+/// assert_eq!(commit.type_(), Type::FEAT);
+/// assert_eq!(commit.footers().next().unwrap().value(), "1");
+///
+/// let err = store.get(1).unwrap().unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::MissingType);
+/// ```
+#[derive(Debug, Default)]
+pub struct CommitStore {
+    options: ParseOptions,
+    text: String,
+    entries: Vec<Entry>,
+    footers: Vec<Slot>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    message: Range<usize>,
+    parsed: Result<Parsed, Error>,
+}
+
+#[derive(Debug)]
+struct Parsed {
+    gitmoji: Option<Range<usize>>,
+    /// `None` when implied by the gitmoji
+    ty: Option<Range<usize>>,
+    scope: Option<Range<usize>>,
+    description: Range<usize>,
+    body: Option<Range<usize>>,
+    breaking: bool,
+    footers: Range<usize>,
+}
+
+#[derive(Debug)]
+struct Slot {
+    token: Range<usize>,
+    sep: FooterSeparator,
+    value: Range<usize>,
+}
+
+impl CommitStore {
+    /// Parse commits according to the Conventional Commit specification.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse commits with extensions to the specification.
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Parse and store `message`, returning its index.
+    pub fn push(&mut self, message: &str) -> usize {
+        self.extend([message]);
+        self.len() - 1
+    }
+
+    /// The number of stored messages, whether or not they parsed.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no messages are stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The raw message at `index`.
+    pub fn message(&self, index: usize) -> Option<&str> {
+        let entry = self.entries.get(index)?;
+        Some(&self.text[entry.message.clone()])
+    }
+
+    /// The commit at `index`, or why its message failed to parse.
+    pub fn get(&self, index: usize) -> Option<Result<StoredCommit<'_>, &Error>> {
+        self.entries.get(index).map(|entry| self.view(entry))
+    }
+
+    /// The commits, in the order they were added.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Result<StoredCommit<'_>, &Error>> + '_ {
+        self.entries.iter().map(|entry| self.view(entry))
+    }
+
+    fn view<'s>(&'s self, entry: &'s Entry) -> Result<StoredCommit<'s>, &'s Error> {
+        let parsed = entry.parsed.as_ref()?;
+        Ok(StoredCommit {
+            text: &self.text,
+            message: &self.text[entry.message.clone()],
+            parsed,
+            footers: &self.footers[parsed.footers.clone()],
+            options: &self.options,
+        })
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for CommitStore {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, messages: I) {
+        let messages: Vec<_> = messages
+            .into_iter()
+            .map(|message| {
+                let start = self.text.len();
+                self.text.push_str(message.as_ref());
+                start..self.text.len()
+            })
+            .collect();
+
+        let text = self.text.as_str();
+        let options = &self.options;
+        #[cfg(feature = "rayon")]
+        let commits: Vec<_> = {
+            use rayon::prelude::*;
+
+            messages
+                .par_iter()
+                .map(|message| Commit::parse_with(&text[message.clone()], options))
+                .collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let commits: Vec<_> = messages
+            .iter()
+            .map(|message| Commit::parse_with(&text[message.clone()], options))
+            .collect();
+
+        self.entries.reserve(messages.len());
+        for (message, commit) in messages.into_iter().zip(commits) {
+            let parsed = commit.map(|commit| record(text, &mut self.footers, &commit));
+            self.entries.push(Entry { message, parsed });
+        }
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for CommitStore {
+    fn from_iter<I: IntoIterator<Item = S>>(messages: I) -> Self {
+        let mut store = Self::new();
+        store.extend(messages);
+        store
+    }
+}
+
+/// Convert the borrows of `commit` into offsets within `text`.
+fn record(text: &str, footers: &mut Vec<Slot>, commit: &Commit<'_>) -> Parsed {
+    let at = |part: &str| span(text, part).expect("commits borrow from their message");
+
+    let start = footers.len();
+    footers.extend(commit.footers().iter().map(|footer| Slot {
+        token: at(footer.token().as_str()),
+        sep: footer.separator(),
+        value: at(footer.value()),
+    }));
+
+    Parsed {
+        gitmoji: commit.gitmoji().map(|gitmoji| at(gitmoji.as_str())),
+        ty: span(text, commit.type_().as_str()),
+        scope: commit.scope().map(|scope| at(scope.as_str())),
+        description: at(commit.description()),
+        body: commit.body().map(at),
+        breaking: commit.breaking(),
+        footers: start..footers.len(),
+    }
+}
+
+/// A commit in a [`CommitStore`].
+///
+/// This covers the common accessors; see [`StoredCommit::commit`] for the rest.
+#[derive(Copy, Clone, Debug)]
+pub struct StoredCommit<'s> {
+    text: &'s str,
+    message: &'s str,
+    parsed: &'s Parsed,
+    footers: &'s [Slot],
+    options: &'s ParseOptions,
+}
+
+impl<'s> StoredCommit<'s> {
+    /// The raw commit message.
+    pub fn message(&self) -> &'s str {
+        self.message
+    }
+
+    /// The gitmoji before the type, when parsed with [`ParseOptions::gitmoji`].
+    pub fn gitmoji(&self) -> Option<Gitmoji<'s>> {
+        let gitmoji = self.parsed.gitmoji.as_ref()?;
+        Some(Gitmoji::new_unchecked(self.slice(gitmoji)))
+    }
+
+    /// The type of the commit.
+    pub fn type_(&self) -> Type<'s> {
+        match &self.parsed.ty {
+            Some(ty) => Type::new_unchecked(self.slice(ty)),
+            None => self
+                .gitmoji()
+                .and_then(|gitmoji| gitmoji.implied_type())
+                .expect("only implied types are stored out of the message"),
+        }
+    }
+
+    /// The optional scope of the commit.
+    pub fn scope(&self) -> Option<Scope<'s>> {
+        let scope = self.parsed.scope.as_ref()?;
+        Some(Scope::new_unchecked(self.slice(scope)))
+    }
+
+    /// The commit description.
+    pub fn description(&self) -> &'s str {
+        self.slice(&self.parsed.description)
+    }
+
+    /// The commit body.
+    pub fn body(&self) -> Option<&'s str> {
+        let body = self.parsed.body.as_ref()?;
+        Some(self.slice(body))
+    }
+
+    /// A flag to signal that the commit contains breaking changes.
+    pub fn breaking(&self) -> bool {
+        self.parsed.breaking
+    }
+
+    /// The footers of the commit.
+    pub fn footers(&self) -> impl ExactSizeIterator<Item = Footer<'s>> + use<'s> {
+        let text = self.text;
+        self.footers.iter().map(move |slot| {
+            Footer::new(
+                FooterToken::new_unchecked(&text[slot.token.clone()]),
+                slot.sep,
+                &text[slot.value.clone()],
+            )
+        })
+    }
+
+    /// The full [`Commit`], re-parsed from the message.
+    pub fn commit(&self) -> Commit<'s> {
+        Commit::parse_with(self.message(), self.options).expect("parsed when stored")
+    }
+
+    fn slice(&self, range: &Range<usize>) -> &'s str {
+        &self.text[range.clone()]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_store() {
+        let mut store = CommitStore::new();
+        store.extend([
+            "feat(ui)!: add dark mode\n\nBody\n\nRefs: #1\nCloses #2",
+            "not conventional",
+        ]);
+        assert_eq!(store.push("fix: handle EOF\n\nAcked-by: Bart"), 2);
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.message(1), Some("not conventional"));
+        assert!(store.get(3).is_none());
+
+        let first = store.get(0).unwrap().unwrap();
+        assert_eq!(first.type_(), Type::FEAT);
+        assert_eq!(first.scope().unwrap(), "ui");
+        assert_eq!(first.description(), "add dark mode");
+        assert_eq!(first.body(), Some("Body"));
+        assert!(first.breaking());
+        let footers: Vec<_> = first.footers().collect();
+        assert_eq!(footers, first.commit().footers());
+        assert_eq!(footers[1].token(), "Closes");
+        assert_eq!(footers[1].separator(), FooterSeparator::Ref);
+
+        let results: Vec<_> = store.iter().map(|result| result.is_ok()).collect();
+        assert_eq!(results, [true, false, true]);
+
+        let last = store.get(2).unwrap().unwrap();
+        assert_eq!(last.message(), "fix: handle EOF\n\nAcked-by: Bart");
+        assert_eq!(last.footers().len(), 1);
+        assert_eq!(last.footers().next().unwrap().value(), "Bart");
+    }
+
+    #[test]
+    fn test_implied_type() {
+        let mut store =
+            CommitStore::with_options(ParseOptions::new().gitmoji(true).implied_types(true));
+        store.push("✨ add dark mode");
+        let commit = store.get(0).unwrap().unwrap();
+        assert_eq!(commit.gitmoji().unwrap(), "✨");
+        assert_eq!(commit.type_(), Type::FEAT);
+        assert_eq!(commit.description(), "add dark mode");
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_parse_batch() {
+        let messages: Vec<_> = (0..100)
+            .map(|i| match i % 3 {
+                0 => format!("feat: thing {i}"),
+                1 => format!("fix: thing {i}\n\nRefs: #{i}"),
+                _ => format!("thing {i}"),
+            })
+            .collect();
+        let commits = Commit::parse_batch(&messages, &ParseOptions::new());
+        assert_eq!(commits.len(), messages.len());
+        for (i, (message, commit)) in messages.iter().zip(&commits).enumerate() {
+            match commit {
+                Ok(commit) => assert_eq!(*commit, Commit::parse(message).unwrap()),
+                Err(_) => assert_eq!(i % 3, 2),
+            }
+        }
+    }
+}
@@ -1,29 +1,45 @@
 //! The conventional commit type and its simple, and typed implementations.
 
-use std::fmt;
-use std::ops::Deref;
-use std::str::FromStr;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use core::ops::Deref;
+use core::str::FromStr;
 
 use winnow::Parser;
+use winnow::combinator::opt;
 use winnow::error::ContextError;
 
-use crate::parser::parse;
-use crate::{Error, ErrorKind};
+#[cfg(feature = "regex")]
+use crate::parser::trailer;
+use crate::parser::{gitmoji, parse, untyped_message};
+use crate::{Error, ErrorKind, Gitmoji, ParseOptions};
 
-const BREAKING_PHRASE: &str = "BREAKING CHANGE";
-const BREAKING_ARROW: &str = "BREAKING-CHANGE";
+pub(crate) const BREAKING_PHRASE: &str = "BREAKING CHANGE";
+pub(crate) const BREAKING_ARROW: &str = "BREAKING-CHANGE";
+/// The footer tokens of breaking changes, by default.
+pub(crate) const NOTES: [&str; 2] = [BREAKING_PHRASE, BREAKING_ARROW];
 
 /// A conventional commit.
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Commit<'a> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ticket: Option<&'a str>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ticket_prefix: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    gitmoji: Option<Gitmoji<'a>>,
     ty: Type<'a>,
     scope: Option<Scope<'a>>,
     description: &'a str,
     body: Option<&'a str>,
     breaking: bool,
     #[cfg_attr(feature = "serde", serde(skip))]
-    breaking_description: Option<&'a str>,
+    exclamation_mark: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    breaking_descriptions: Vec<&'a str>,
     footers: Vec<Footer<'a>>,
 }
 
@@ -36,15 +52,100 @@ impl<'a> Commit<'a> {
     /// This function returns an error if the commit does not conform to the
     /// Conventional Commit specification.
     pub fn parse(string: &'a str) -> Result<Self, Error> {
-        let (ty, scope, breaking, description, body, footers) = parse::<ContextError>
-            .parse(string)
-            .map_err(|err| Error::with_nom(string, err))?;
+        Self::parse_with(string, &ParseOptions::new())
+    }
 
-        let breaking_description = footers
+    /// Create a new Conventional Commit based on the provided commit message
+    /// string, with extensions to the specification.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the commit does not conform to the
+    /// Conventional Commit specification, as extended by `options`.
+    pub fn parse_with(string: &'a str, options: &ParseOptions) -> Result<Self, Error> {
+        let notes = options.notes();
+        let notes = &*notes;
+        let mut rest = string;
+        let mut ticket = None;
+        if let Some(pattern) = options.ticket_pattern().filter(|p| p.is_prefix()) {
+            if let Some((key, after)) = pattern.split(rest) {
+                ticket = Some(key);
+                rest = after;
+            }
+        }
+        let ticket_prefix = ticket.is_some();
+        let gitmoji = if options.is_gitmoji() {
+            opt(gitmoji::<ContextError>)
+                .parse_next(&mut rest)
+                .ok()
+                .flatten()
+                .map(Gitmoji::new_unchecked)
+        } else {
+            None
+        };
+        let with_nom = |input: &str, err| {
+            let err = Error::with_nom(input, err);
+            let offset = string.len() - input.len() + err.offset().unwrap_or(0);
+            err.set_commit(string, offset)
+        };
+
+        #[cfg(feature = "regex")]
+        let header = options
+            .header_pattern()
+            .map(|header| {
+                let end = rest.find(['\r', '\n']).unwrap_or(rest.len());
+                let (summary, after) = rest.split_at(end);
+                let (ty, scope, breaking, description) = header
+                    .parse(summary)
+                    .map_err(|err| err.set_commit(string, string.len() - rest.len()))?;
+                let (body, footers) = (|i: &mut &'a str| trailer::<ContextError>(notes, i))
+                    .parse(after)
+                    .map_err(|err| with_nom(after, err))?;
+                Ok::<_, Error>((ty, scope, breaking, description, body, footers))
+            })
+            .transpose()?;
+        #[cfg(not(feature = "regex"))]
+        let header = None;
+
+        let implied_type = gitmoji
+            .filter(|_| options.is_implied_types())
+            .and_then(|gitmoji| gitmoji.implied_type());
+        let (ty, scope, breaking, description, body, footers) = match header {
+            Some(details) => details,
+            None => match (
+                (|i: &mut &'a str| parse::<ContextError>(notes, i)).parse(rest),
+                implied_type,
+            ) {
+                (Ok(details), _) => details,
+                (Err(_), Some(ty)) => {
+                    let (description, (body, footers)) =
+                        (|i: &mut &'a str| untyped_message::<ContextError>(notes, i))
+                            .parse(rest)
+                            .map_err(|err| with_nom(rest, err))?;
+                    (ty.as_str(), None, false, description, body, footers)
+                }
+                (Err(err), None) => return Err(with_nom(rest, err)),
+            },
+        };
+
+        let mut description = description;
+        if let Some(pattern) = options.ticket_pattern().filter(|p| p.is_suffix()) {
+            if let Some((key, after)) = pattern.split(description).filter(|_| ticket.is_none()) {
+                ticket = Some(key);
+                description = after;
+            }
+        }
+
+        let mut breaking_descriptions: Vec<_> = footers
             .iter()
-            .find_map(|(k, _, v)| (k == &BREAKING_PHRASE || k == &BREAKING_ARROW).then_some(*v))
-            .or_else(|| breaking.then_some(description));
-        let breaking = breaking_description.is_some();
+            .filter(|(k, _, _)| notes.contains(k))
+            .map(|(_, _, v)| *v)
+            .collect();
+        if breaking_descriptions.is_empty() && breaking {
+            breaking_descriptions.push(description);
+        }
+        let exclamation_mark = breaking;
+        let breaking = !breaking_descriptions.is_empty();
         let footers: Result<Vec<_>, Error> = footers
             .into_iter()
             .map(|(k, s, v)| Ok(Footer::new(FooterToken::new_unchecked(k), s.parse()?, v)))
@@ -52,16 +153,37 @@ impl<'a> Commit<'a> {
         let footers = footers?;
 
         Ok(Self {
+            ticket,
+            ticket_prefix,
+            gitmoji,
             ty: Type::new_unchecked(ty),
             scope: scope.map(Scope::new_unchecked),
             description,
             body,
             breaking,
-            breaking_description,
+            exclamation_mark,
+            breaking_descriptions,
             footers,
         })
     }
 
+    /// The ticket key before the type or description, like `PROJ-42`, when parsed with
+    /// [`ParseOptions::ticket`].
+    pub fn ticket(&self) -> Option<&'a str> {
+        self.ticket
+    }
+
+    /// Whether the ticket key came before the type, rather than the description.
+    pub(crate) fn ticket_prefix(&self) -> bool {
+        self.ticket_prefix
+    }
+
+    /// The [gitmoji](https://gitmoji.dev) before the type, when parsed with
+    /// [`ParseOptions::gitmoji`].
+    pub fn gitmoji(&self) -> Option<Gitmoji<'a>> {
+        self.gitmoji
+    }
+
     /// The type of the commit.
     pub fn type_(&self) -> Type<'a> {
         self.ty
@@ -101,12 +223,25 @@ impl<'a> Commit<'a> {
         self.breaking
     }
 
+    /// Whether the summary marks the commit as breaking with a `!`.
+    pub(crate) fn exclamation_mark(&self) -> bool {
+        self.exclamation_mark
+    }
+
     /// Explanation for the breaking change.
     ///
     /// Note: if no `BREAKING CHANGE` footer is provided, the `description` is expected to describe
     /// the breaking change.
     pub fn breaking_description(&self) -> Option<&'a str> {
-        self.breaking_description
+        self.breaking_descriptions.first().copied()
+    }
+
+    /// Explanations for every breaking change, in order.
+    ///
+    /// Note: if no `BREAKING CHANGE` footer is provided, the `description` is expected to describe
+    /// the breaking change.
+    pub fn breaking_descriptions(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.breaking_descriptions.iter().copied()
     }
 
     /// Any footer.
@@ -118,6 +253,45 @@ impl<'a> Commit<'a> {
     pub fn footers(&self) -> &[Footer<'a>] {
         &self.footers
     }
+
+    /// The first footer with a matching token, ignoring case.
+    pub fn footer(&self, token: &str) -> Option<&Footer<'a>> {
+        self.footers.iter().find(|f| f.token() == token)
+    }
+
+    /// The values of every footer with a matching token, ignoring case, in order.
+    ///
+    /// ```rust
+    /// let commit = git_conventional::Commit::parse(
+    ///     "feat: pair on it\n\nCo-authored-by: Lisa\nRefs: #1\nco-authored-by: Bart",
+    /// ).unwrap();
+    /// let co_authors: Vec<_> = commit.footer_values("Co-Authored-By").collect();
+    /// assert_eq!(co_authors, ["Lisa", "Bart"]);
+    /// ```
+    pub fn footer_values<'s>(&'s self, token: &'s str) -> impl Iterator<Item = &'a str> + 's {
+        self.footers
+            .iter()
+            .filter(move |f| f.token() == token)
+            .map(|f| f.value())
+    }
+
+    /// The footers grouped by token, ignoring case.
+    ///
+    /// Groups are ordered by the first appearance of their token, with each group keeping the
+    /// footers in order.
+    pub fn footer_groups(&self) -> Vec<(FooterToken<'a>, Vec<&Footer<'a>>)> {
+        let mut groups: Vec<(FooterToken<'a>, Vec<&Footer<'a>>)> = Vec::new();
+        for footer in &self.footers {
+            match groups
+                .iter_mut()
+                .find(|(token, _)| *token == footer.token())
+            {
+                Some((_, group)) => group.push(footer),
+                None => groups.push((footer.token(), vec![footer])),
+            }
+        }
+        groups
+    }
 }
 
 impl fmt::Display for Commit<'_> {
@@ -178,6 +352,8 @@ impl<'a> Footer<'a> {
     }
 
     /// A flag to signal that the footer describes a breaking change.
+    ///
+    /// This ignores [`ParseOptions::note_keywords`], see [`Commit::breaking_descriptions`].
     pub fn breaking(&self) -> bool {
         self.token.breaking()
     }
@@ -360,7 +536,8 @@ impl<'a> FooterToken<'a> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::ErrorKind;
+    use crate::{ErrorKind, TicketPattern};
+    use alloc::string::String;
     use indoc::indoc;
     #[cfg(feature = "serde")]
     use serde_test::Token;
@@ -481,6 +658,201 @@ mod test {
         assert_eq!(commit.breaking_description(), Some("it's broken"));
     }
 
+    #[test]
+    fn test_multiple_breaking_changes() {
+        let commit = Commit::parse(indoc!(
+            "feat!: message
+
+            BREAKING CHANGE: first
+            Refs: #1
+            BREAKING-CHANGE: second"
+        ))
+        .unwrap();
+        assert_eq!(commit.breaking_description(), Some("first"));
+        assert_eq!(
+            commit.breaking_descriptions().collect::<Vec<_>>(),
+            ["first", "second"]
+        );
+
+        let commit = Commit::parse("feat!: message").unwrap();
+        assert_eq!(
+            commit.breaking_descriptions().collect::<Vec<_>>(),
+            ["message"]
+        );
+
+        let commit = Commit::parse("feat: message").unwrap();
+        assert_eq!(commit.breaking_descriptions().count(), 0);
+    }
+
+    #[test]
+    fn test_gitmoji() {
+        let options = ParseOptions::new().gitmoji(true);
+
+        let commit = Commit::parse_with("✨ feat(ui): add dark mode", &options).unwrap();
+        assert_eq!(commit.gitmoji().unwrap(), "✨");
+        assert_eq!(commit.type_(), Type::FEAT);
+        assert_eq!(commit.scope().unwrap(), "ui");
+
+        let commit = Commit::parse_with(":bug:fix: handle EOF\n\nbody", &options).unwrap();
+        assert_eq!(commit.gitmoji().unwrap(), ":bug:");
+        assert_eq!(commit.type_(), Type::FIX);
+        assert_eq!(commit.body(), Some("body"));
+
+        let commit = Commit::parse_with("fix: handle EOF", &options).unwrap();
+        assert_eq!(commit.gitmoji(), None);
+
+        assert!(Commit::parse("✨ feat: add dark mode").is_err());
+
+        let err = Commit::parse_with("✨ add dark mode", &options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingType);
+        assert_eq!(err.offset(), Some("✨ add".len()));
+    }
+
+    #[test]
+    fn test_gitmoji_implied_type() {
+        let options = ParseOptions::new().gitmoji(true).implied_types(true);
+
+        let commit = Commit::parse_with("⚡️ cache lookups\n\nRefs: #1\n", &options).unwrap();
+        assert_eq!(commit.type_(), Type::PERF);
+        assert_eq!(commit.scope(), None);
+        assert_eq!(commit.description(), "cache lookups");
+        assert_eq!(commit.footers().len(), 1);
+
+        let commit = Commit::parse_with("✨ docs: explain", &options).unwrap();
+        assert_eq!(commit.type_(), Type::DOCS);
+
+        let err = Commit::parse_with("🚀 deploy", &options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingType);
+
+        let err = Commit::parse_with("✨ ", &options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingDescription);
+    }
+
+    #[test]
+    fn test_ticket() {
+        let options = ParseOptions::new().ticket(TicketPattern::new().any_project());
+
+        let commit = Commit::parse_with("PROJ-42 feat(api)!: x", &options).unwrap();
+        assert_eq!(commit.ticket(), Some("PROJ-42"));
+        assert_eq!(commit.type_(), Type::FEAT);
+        assert_eq!(commit.breaking_description(), Some("x"));
+
+        let commit = Commit::parse_with("feat(api)!: PROJ-42: x", &options).unwrap();
+        assert_eq!(commit.ticket(), Some("PROJ-42"));
+        assert_eq!(commit.description(), "x");
+        assert_eq!(commit.breaking_description(), Some("x"));
+
+        let commit = Commit::parse_with("feat: PROJ-42", &options).unwrap();
+        assert_eq!(commit.ticket(), None);
+        assert_eq!(commit.description(), "PROJ-42");
+
+        let options = ParseOptions::new().ticket(TicketPattern::new().projects(["PROJ"]));
+        let commit = Commit::parse_with("feat: UTF-8 support", &options).unwrap();
+        assert_eq!(commit.ticket(), None);
+        assert_eq!(commit.description(), "UTF-8 support");
+        let commit = Commit::parse_with("PROJ-42 feat: x", &options).unwrap();
+        assert_eq!(commit.ticket(), Some("PROJ-42"));
+
+        let options = ParseOptions::new().ticket(TicketPattern::new());
+        let commit = Commit::parse_with("feat: PROJ-42 x", &options).unwrap();
+        assert_eq!(commit.ticket(), None);
+
+        let options = ParseOptions::new()
+            .gitmoji(true)
+            .ticket(TicketPattern::new().any_project().suffix(false));
+        let commit = Commit::parse_with("[PROJ-1] ✨ feat: PROJ-2 x", &options).unwrap();
+        assert_eq!(commit.ticket(), Some("PROJ-1"));
+        assert_eq!(commit.gitmoji().unwrap(), "✨");
+        assert_eq!(commit.description(), "PROJ-2 x");
+
+        let err = Commit::parse_with("PROJ-42 add thing", &options).unwrap_err();
+        assert_eq!(err.offset(), Some("PROJ-42 add".len()));
+    }
+
+    #[test]
+    fn test_note_keywords() {
+        let message = indoc! {"
+            feat: x
+
+            BREAKING CHANGES: one
+
+            BREAKING CHANGE: two
+        "};
+        let commit = Commit::parse(message).unwrap();
+        assert_eq!(commit.footers().len(), 1);
+        assert_eq!(commit.breaking_descriptions().collect::<Vec<_>>(), ["two"]);
+
+        let options = ParseOptions::new().note_keywords(["BREAKING CHANGES"]);
+        let commit = Commit::parse_with(message, &options).unwrap();
+        assert_eq!(commit.footers().len(), 2);
+        assert_eq!(commit.footers()[0].token(), "BREAKING CHANGES");
+        assert_eq!(commit.breaking_descriptions().collect::<Vec<_>>(), ["one"]);
+
+        // Like keywords loaded from a preset at runtime
+        let keywords: Vec<String> = "DEPRECATED".split(',').map(String::from).collect();
+        let options = ParseOptions::new().note_keywords(keywords);
+        let commit = Commit::parse_with("feat: x\n\nBREAKING-CHANGE: y", &options).unwrap();
+        assert!(!commit.breaking());
+        let commit = Commit::parse_with("feat!: x\n\nDEPRECATED: y", &options).unwrap();
+        assert_eq!(commit.breaking_descriptions().collect::<Vec<_>>(), ["y"]);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_header_pattern() {
+        let options = ParseOptions::new()
+            .header(crate::HeaderPattern::conventional())
+            .gitmoji(true);
+        let commit =
+            Commit::parse_with("✨ feat(ui)!: dark mode\n\nbody\n\nRefs: #1", &options).unwrap();
+        assert_eq!(commit.gitmoji().unwrap(), "✨");
+        assert_eq!(commit.type_(), Type::FEAT);
+        assert_eq!(commit.scope().unwrap(), "ui");
+        assert_eq!(commit.breaking_description(), Some("dark mode"));
+        assert_eq!(commit.body(), Some("body"));
+        assert_eq!(commit.footers().len(), 1);
+
+        let err = Commit::parse_with("✨ dark mode", &options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFormat);
+        assert_eq!(err.offset(), Some("✨ ".len()));
+    }
+
+    #[test]
+    fn test_footer_lookup() {
+        let commit = Commit::parse(indoc!(
+            "feat: message
+
+            Co-authored-by: Lisa
+            Refs #1
+            CO-AUTHORED-BY: Bart
+            Refs: #2"
+        ))
+        .unwrap();
+        assert_eq!(commit.footer("refs").unwrap().value(), "1");
+        assert_eq!(commit.footer("Signed-off-by"), None);
+        assert_eq!(
+            commit.footer_values("co-authored-by").collect::<Vec<_>>(),
+            ["Lisa", "Bart"]
+        );
+
+        let groups = commit.footer_groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "Co-authored-by");
+        assert_eq!(
+            groups[0].1.iter().map(|f| f.value()).collect::<Vec<_>>(),
+            ["Lisa", "Bart"]
+        );
+        assert_eq!(groups[1].0, "Refs");
+        assert_eq!(
+            groups[1]
+                .1
+                .iter()
+                .map(|f| f.separator())
+                .collect::<Vec<_>>(),
+            [FooterSeparator::Ref, FooterSeparator::Value]
+        );
+    }
+
     #[test]
     fn test_conjoined_footer() {
         let commit = Commit::parse(
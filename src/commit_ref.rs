@@ -0,0 +1,203 @@
+//! A conventional commit parsed without allocating.
+
+use core::fmt;
+
+use winnow::Parser;
+
+use crate::commit::NOTES;
+use crate::error::KindError;
+use crate::parser::{footer, lazy_message};
+use crate::{ErrorRef, Footer, FooterToken, Scope, Type};
+
+/// A conventional commit, parsed without allocating.
+///
+/// Footers are parsed on demand, each time they are iterated, and errors borrow the message
+/// rather than copying it.  This follows the specification, like [`Commit::parse`].
+///
+/// [`Commit::parse`]: crate::Commit::parse
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{CommitRef, ErrorKind, Type};
+///
+/// let message = "feat!: drop the old API\n\nRefs: #12\nBREAKING CHANGE: use `v2` instead";
+/// let commit = CommitRef::parse(message).unwrap();
+/// assert_eq!(commit.type_(), Type::FEAT);
+/// assert!(commit.breaking());
+/// assert_eq!(commit.breaking_description(), Some("use `v2` instead"));
+///
+/// let tokens: Vec<_> = commit.footers().map(|f| f.token().as_str()).collect();
+/// assert_eq!(tokens, ["Refs", "BREAKING CHANGE"]);
+///
+/// let err = CommitRef::parse("drop the old API").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::MissingType);
+/// assert_eq!(err.commit(), "drop the old API");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CommitRef<'a> {
+    ty: Type<'a>,
+    scope: Option<Scope<'a>>,
+    description: &'a str,
+    body: Option<&'a str>,
+    exclamation_mark: bool,
+    footers: &'a str,
+}
+
+impl<'a> CommitRef<'a> {
+    /// Parse a commit message.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the commit does not conform to the
+    /// Conventional Commit specification.
+    pub fn parse(string: &'a str) -> Result<Self, ErrorRef<'a>> {
+        let (ty, scope, exclamation_mark, description, body, footers) =
+            (|i: &mut &'a str| lazy_message::<KindError>(&NOTES, i))
+                .parse(string)
+                .map_err(|err| ErrorRef::new(err.inner().kind(), string, err.offset()))?;
+
+        Ok(Self {
+            ty: Type::new_unchecked(ty),
+            scope: scope.map(Scope::new_unchecked),
+            description,
+            body,
+            exclamation_mark,
+            footers,
+        })
+    }
+
+    /// The type of the commit.
+    pub fn type_(&self) -> Type<'a> {
+        self.ty
+    }
+
+    /// The optional scope of the commit.
+    pub fn scope(&self) -> Option<Scope<'a>> {
+        self.scope
+    }
+
+    /// The commit description.
+    pub fn description(&self) -> &'a str {
+        self.description
+    }
+
+    /// The commit body, containing a more detailed explanation of the commit
+    /// changes.
+    pub fn body(&self) -> Option<&'a str> {
+        self.body
+    }
+
+    /// A flag to signal that the commit contains breaking changes, see [`Commit::breaking`].
+    ///
+    /// [`Commit::breaking`]: crate::Commit::breaking
+    pub fn breaking(&self) -> bool {
+        self.breaking_description().is_some()
+    }
+
+    /// Explanation for the breaking change.
+    ///
+    /// Note: if no `BREAKING CHANGE` footer is provided, the `description` is expected to describe
+    /// the breaking change.
+    pub fn breaking_description(&self) -> Option<&'a str> {
+        self.footers()
+            .find(|f| NOTES.contains(&f.token().as_str()))
+            .map(|f| f.value())
+            .or_else(|| self.exclamation_mark.then_some(self.description))
+    }
+
+    /// Any footer, parsed as it is iterated.
+    pub fn footers(&self) -> impl Iterator<Item = Footer<'a>> + use<'a> {
+        let mut rest = self.footers;
+        core::iter::from_fn(move || {
+            let (token, sep, value) = footer::<()>(&NOTES, &mut rest).ok()?;
+            Some(Footer::new(
+                FooterToken::new_unchecked(token),
+                sep.parse().ok()?,
+                value,
+            ))
+        })
+    }
+
+    /// The first footer with a matching token, ignoring case.
+    pub fn footer(&self, token: &str) -> Option<Footer<'a>> {
+        self.footers().find(|f| f.token() == token)
+    }
+}
+
+impl fmt::Display for CommitRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.type_().as_str())?;
+
+        if let Some(scope) = &self.scope() {
+            f.write_fmt(format_args!("({scope})"))?;
+        }
+
+        f.write_fmt(format_args!(": {}", self.description()))?;
+
+        if let Some(body) = &self.body() {
+            f.write_fmt(format_args!("\n\n{body}"))?;
+        }
+
+        for footer in self.footers() {
+            write!(f, "\n\n{footer}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Commit;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_matches_commit() {
+        let messages = [
+            "feat: add thing",
+            "feat(ui)!: add dark mode\n\nBody\n\nwith paragraphs\n\nRefs: #1\nCloses #2",
+            "fix: handle EOF\n\nBREAKING-CHANGE: first\n  continued\nBREAKING CHANGE: second\n\n",
+            "fix!: handle EOF\n\nAcked-by: Bart",
+            "chore: release\r\n\r\nSigned-off-by: Lisa\r\n",
+            "",
+            "feat",
+            "feat(: x",
+            "feat: ",
+            "feat: x\nbody",
+            "feat: x\n\nBody\n\nRefs: #1\nnot a footer",
+        ];
+        for message in messages {
+            match (CommitRef::parse(message), Commit::parse(message)) {
+                (Ok(commit_ref), Ok(commit)) => {
+                    assert_eq!(commit_ref.type_(), commit.type_(), "{message:?}");
+                    assert_eq!(commit_ref.scope(), commit.scope(), "{message:?}");
+                    assert_eq!(commit_ref.description(), commit.description());
+                    assert_eq!(commit_ref.body(), commit.body(), "{message:?}");
+                    assert_eq!(commit_ref.breaking(), commit.breaking(), "{message:?}");
+                    assert_eq!(
+                        commit_ref.breaking_description(),
+                        commit.breaking_description(),
+                        "{message:?}"
+                    );
+                    assert_eq!(
+                        commit_ref.footers().collect::<Vec<_>>(),
+                        commit.footers(),
+                        "{message:?}"
+                    );
+                    assert_eq!(commit_ref.to_string(), commit.to_string());
+                }
+                (Err(err_ref), Err(err)) => {
+                    assert_eq!(err_ref.kind(), err.kind(), "{message:?}");
+                    assert_eq!(Some(err_ref.offset()), err.offset(), "{message:?}");
+                    assert_eq!(err_ref.commit(), message);
+                }
+                (commit_ref, commit) => {
+                    panic!("{message:?}: {commit_ref:?} != {commit:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,362 @@
+//! Completions learned from commit history.
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use unicase::UniCase;
+
+use crate::commit::NOTES;
+use crate::lines::LinesWithTerminator;
+use crate::parser::footer_start;
+use crate::{Commit, TypeRegistry};
+
+/// Completes commit messages with the types, scopes, and footer tokens of earlier commits.
+///
+/// Suggestions are ranked by how often they were used, ignoring case like [`Type`] and
+/// [`Scope`] do.  Ties keep the order types were first seen in, and the alphabetical order of
+/// scopes and footer tokens.
+///
+/// [`Type`]: crate::Type
+/// [`Scope`]: crate::Scope
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Commit, Completer, CompletionKind};
+///
+/// let history = ["fix(api): a", "fix(api): b", "fix(app): c", "feat(ui): d", "fix: e"];
+/// let commits: Vec<_> = history.iter().map(|m| Commit::parse(m).unwrap()).collect();
+/// let completer: Completer = commits.iter().collect();
+///
+/// let completions = completer.complete("fix(ap");
+/// let scopes: Vec<_> = completions.iter().map(|c| c.text()).collect();
+/// assert_eq!(scopes, ["api", "app"]);
+/// assert_eq!(completions[0].kind(), CompletionKind::Scope);
+/// assert_eq!(completions[0].range(), 4..6);
+///
+/// let completions = completer.complete("f");
+/// let types: Vec<_> = completions.iter().map(|c| (c.text(), c.count())).collect();
+/// assert_eq!(types, [("fix", 4), ("feat", 1)]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Completer {
+    types: BTreeMap<UniCase<String>, TypeCounts>,
+    scopes: BTreeMap<UniCase<String>, usize>,
+    footer_tokens: BTreeMap<UniCase<String>, usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct TypeCounts {
+    /// When the type was first seen, to break ties
+    order: usize,
+    count: usize,
+    scopes: BTreeMap<UniCase<String>, usize>,
+}
+
+impl Completer {
+    /// Complete nothing until commits are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also complete the types of `registry`, after any types used by earlier commits.
+    ///
+    /// Unused types are completed in the order of the registry.
+    pub fn known_types(mut self, registry: &TypeRegistry) -> Self {
+        for info in registry {
+            self.type_counts(info.name());
+        }
+        self
+    }
+
+    /// Also complete `tokens` as footer tokens, after any used by earlier commits.
+    pub fn known_footer_tokens<'t>(mut self, tokens: impl IntoIterator<Item = &'t str>) -> Self {
+        for token in tokens {
+            self.footer_tokens
+                .entry(UniCase::new(token.to_owned()))
+                .or_default();
+        }
+        self
+    }
+
+    /// Learn from an earlier commit.
+    pub fn add(&mut self, commit: &Commit<'_>) {
+        let ty = self.type_counts(commit.type_().as_str());
+        ty.count += 1;
+        if let Some(scope) = commit.scope() {
+            let scope = UniCase::new(scope.as_str().to_owned());
+            *ty.scopes.entry(scope.clone()).or_default() += 1;
+            *self.scopes.entry(scope).or_default() += 1;
+        }
+        for footer in commit.footers() {
+            *self
+                .footer_tokens
+                .entry(UniCase::new(footer.token().as_str().to_owned()))
+                .or_default() += 1;
+        }
+    }
+
+    fn type_counts(&mut self, ty: &str) -> &mut TypeCounts {
+        let order = self.types.len();
+        self.types
+            .entry(UniCase::new(ty.to_owned()))
+            .or_insert_with(|| TypeCounts {
+                order,
+                ..TypeCounts::default()
+            })
+    }
+
+    /// Complete the word before the end of `partial`, a commit message being typed.
+    ///
+    /// On the summary line, this completes the type, or the scope after `type(`.  Scopes used
+    /// with the type come first, followed by those only used with other types.  On a line
+    /// starting a footer, this completes the footer's token.
+    pub fn complete(&self, partial: &str) -> Vec<Completion> {
+        let line_start = partial.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line = &partial[line_start..];
+
+        if line_start == 0 {
+            if !line.contains(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ':' | '!')) {
+                let mut types: Vec<_> = self.types.iter().collect();
+                types.sort_by_key(|(_, counts)| counts.order);
+                let types = types.into_iter().map(|(ty, counts)| (ty, counts.count));
+                return rank(types, line, CompletionKind::Type, 0..partial.len());
+            }
+
+            let Some((ty, scope)) = line.split_once('(') else {
+                return Vec::new();
+            };
+            if scope.contains([')', '\r']) {
+                return Vec::new();
+            }
+            let range = partial.len() - scope.len()..partial.len();
+            let used = self.types.get(&UniCase::new(ty.to_owned()));
+            let with_type = |name| {
+                used.and_then(|used| used.scopes.get(name).copied())
+                    .unwrap_or(0)
+            };
+            let (used, others): (Vec<_>, Vec<_>) = self
+                .scopes
+                .iter()
+                .map(|(name, total)| (name, with_type(name), *total))
+                .partition(|(_, count, _)| 0 < *count);
+            let used = used.into_iter().map(|(name, count, _)| (name, count));
+            let others = others.into_iter().map(|(name, _, total)| (name, total));
+            let mut completions = rank(used, scope, CompletionKind::Scope, range.clone());
+            completions.extend(rank(others, scope, CompletionKind::Scope, range));
+            return completions;
+        }
+
+        // Like `parser::body`, footers start on a line after a blank one, but never right after
+        // the summary, and run to the end of the message
+        let mut prior_is_empty = false;
+        let mut in_footers = false;
+        for previous in LinesWithTerminator::new(&partial[..line_start]).skip(1) {
+            if prior_is_empty && footer_start(&NOTES, previous).is_some() {
+                in_footers = true;
+                break;
+            }
+            prior_is_empty = previous.trim().is_empty();
+        }
+        let after_footer = in_footers || prior_is_empty;
+        let is_token = line
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ' '));
+        if !after_footer || line.starts_with(' ') || !is_token {
+            return Vec::new();
+        }
+        rank(
+            self.footer_tokens.iter().map(count),
+            line,
+            CompletionKind::FooterToken,
+            line_start..partial.len(),
+        )
+    }
+}
+
+fn count<'a>((key, count): (&'a UniCase<String>, &usize)) -> (&'a UniCase<String>, usize) {
+    (key, *count)
+}
+
+/// Completions for the keys starting with `prefix`, most used first.
+fn rank<'a>(
+    counts: impl Iterator<Item = (&'a UniCase<String>, usize)>,
+    prefix: &str,
+    kind: CompletionKind,
+    range: Range<usize>,
+) -> Vec<Completion> {
+    let prefix = prefix.to_lowercase();
+    let mut completions: Vec<_> = counts
+        .filter(|(key, _)| key.to_lowercase().starts_with(&prefix))
+        .map(|(key, count)| Completion {
+            kind,
+            text: key.as_str().to_owned(),
+            range: range.clone(),
+            count,
+        })
+        .collect();
+    // Stable, so ties stay in the order given
+    completions.sort_by_key(|completion| core::cmp::Reverse(completion.count));
+    completions
+}
+
+impl<'a, 'c> Extend<&'a Commit<'c>> for Completer {
+    fn extend<I: IntoIterator<Item = &'a Commit<'c>>>(&mut self, commits: I) {
+        for commit in commits {
+            self.add(commit);
+        }
+    }
+}
+
+impl<'a, 'c> FromIterator<&'a Commit<'c>> for Completer {
+    fn from_iter<I: IntoIterator<Item = &'a Commit<'c>>>(commits: I) -> Self {
+        let mut completer = Self::new();
+        completer.extend(commits);
+        completer
+    }
+}
+
+/// A suggestion from [`Completer::complete`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    kind: CompletionKind,
+    text: String,
+    range: Range<usize>,
+    count: usize,
+}
+
+impl Completion {
+    /// What is being completed.
+    pub fn kind(&self) -> CompletionKind {
+        self.kind
+    }
+
+    /// The suggested text, as it was first written.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The byte range of the partial message that the text replaces.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// How many earlier commits used this, in the context it was ranked in.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// What a [`Completion`] completes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CompletionKind {
+    /// The type of the commit
+    Type,
+    /// The scope of the commit
+    Scope,
+    /// The token of a footer
+    FooterToken,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn completer(history: &[&str]) -> Completer {
+        let commits: Vec<_> = history.iter().map(|m| Commit::parse(m).unwrap()).collect();
+        commits.iter().collect()
+    }
+
+    fn texts(completions: &[Completion]) -> Vec<(&str, usize)> {
+        completions.iter().map(|c| (c.text(), c.count())).collect()
+    }
+
+    #[test]
+    fn test_types() {
+        let completer = completer(&["fix: a", "Fix: b", "feat: c", "docs: d"])
+            .known_types(&TypeRegistry::conventional());
+        let completions = completer.complete("F");
+        assert_eq!(texts(&completions), [("fix", 2), ("feat", 1)]);
+        assert_eq!(completions[0].range(), 0..1);
+
+        let completions = completer.complete("");
+        assert_eq!(completions[0].text(), "fix");
+        assert_eq!(
+            texts(&completions[..4]),
+            [("fix", 2), ("feat", 1), ("docs", 1), ("perf", 0)]
+        );
+        assert_eq!(completions.last().unwrap().count(), 0);
+
+        // Unused types keep the order of the registry
+        let registry = TypeRegistry::conventional();
+        let completer = Completer::new().known_types(&registry);
+        let types: Vec<_> = completer.complete("").into_iter().map(|c| c.text).collect();
+        let known: Vec<_> = registry.iter().map(|info| info.name().to_owned()).collect();
+        assert_eq!(types, known);
+        assert!(completer.complete("fix: a").is_empty());
+    }
+
+    #[test]
+    fn test_scopes() {
+        let completer = completer(&[
+            "fix(api): a",
+            "feat(API): b",
+            "feat(app): c",
+            "feat(app): d",
+            "docs(apple): e",
+        ]);
+        let completions = completer.complete("fix(a");
+        assert_eq!(texts(&completions), [("api", 1), ("app", 2), ("apple", 1)]);
+        assert_eq!(completions[0].range(), 4..5);
+
+        let completions = completer.complete("chore(");
+        assert_eq!(texts(&completions), [("api", 2), ("app", 2), ("apple", 1)]);
+        assert!(completer.complete("fix(api) ").is_empty());
+    }
+
+    #[test]
+    fn test_footer_tokens() {
+        let completer = completer(&[
+            "fix: a\n\nRefs: #1",
+            "fix: b\n\nRefs: #2\nReviewed-by: Lisa",
+            "fix: c\n\nBREAKING CHANGE: x",
+        ]);
+        let partial = "fix: d\n\nBody\n\nRe";
+        let completions = completer.complete(partial);
+        assert_eq!(texts(&completions), [("Refs", 2), ("Reviewed-by", 1)]);
+        assert_eq!(completions[0].range(), partial.len() - 2..partial.len());
+
+        assert_eq!(completer.complete("fix: d\n\nRefs: #1\nB").len(), 1);
+        assert!(completer.complete("fix: d\n\nBody\nRe").is_empty());
+        assert!(completer.complete("fix: d\n\nRefs: #").is_empty());
+        assert!(completer.complete("fix: d\nRe").is_empty());
+        assert!(
+            completer
+                .complete("fix: d\n\nBody\nRefs: #1\nRe")
+                .is_empty()
+        );
+        assert_eq!(
+            completer
+                .complete("fix: d\n\nRefs: #1\n  continued\n\nBody-like\nRe")
+                .len(),
+            2
+        );
+
+        let completer = completer.known_footer_tokens(["Closes", "refs"]);
+        let completions = completer.complete("fix: d\n\n");
+        assert_eq!(
+            texts(&completions),
+            [
+                ("Refs", 2),
+                ("BREAKING CHANGE", 1),
+                ("Reviewed-by", 1),
+                ("Closes", 0)
+            ]
+        );
+        assert!(completer.complete("fix: d\n\nBody\nC").is_empty());
+    }
+}
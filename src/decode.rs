@@ -0,0 +1,327 @@
+//! Commit messages in encodings other than UTF-8, like git's `i18n.commitEncoding`.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::{Commit, Error, ParseOptions};
+
+/// Options for [`Decoded::parse`].
+///
+/// By default, messages are strict UTF-8, like git's default `i18n.commitEncoding`.
+///
+/// Without the `encoding_rs` feature, only UTF-8 and ISO-8859-1 are recognized.  With it, any
+/// [WHATWG label](https://encoding.spec.whatwg.org/#names-and-labels) is, like `Shift_JIS`,
+/// except that ISO-8859-1 stays itself rather than becoming windows-1252.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    encoding: Option<String>,
+    lossy: bool,
+}
+
+impl DecodeOptions {
+    /// Decode strict UTF-8.
+    pub const fn new() -> Self {
+        Self {
+            encoding: None,
+            lossy: false,
+        }
+    }
+
+    /// Decode messages in `encoding`, like the `encoding` header of a git commit object.
+    ///
+    /// `None` means UTF-8.
+    pub fn encoding(mut self, encoding: Option<&str>) -> Self {
+        self.encoding = encoding.map(Into::into);
+        self
+    }
+
+    /// Replace malformed byte sequences with `U+FFFD`, rather than failing.
+    ///
+    /// The replaced bytes are listed in [`Decoded::malformed`].
+    pub const fn lossy(mut self, yes: bool) -> Self {
+        self.lossy = yes;
+        self
+    }
+}
+
+/// A commit message decoded from bytes.
+///
+/// Decoding is not a `Commit::parse_bytes`, as a [`Commit`] borrows its message, which must be
+/// decoded first.  Keep the `Decoded` and parse it with [`Decoded::commit`] instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded<'a> {
+    text: Cow<'a, str>,
+    malformed: Vec<Range<usize>>,
+}
+
+impl<'a> Decoded<'a> {
+    /// Decode a commit message from bytes, to be parsed with [`Decoded::commit`].
+    ///
+    /// Decoding issues are reported here, or by [`Decoded::malformed`] when
+    /// [lossy][DecodeOptions::lossy], keeping them apart from errors in the commit's grammar.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the encoding is not recognized, or if the message is
+    /// malformed in it and decoding is not lossy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use git_conventional::{DecodeOptions, Decoded};
+    ///
+    /// let options = DecodeOptions::new().encoding(Some("ISO-8859-1"));
+    /// let decoded = Decoded::parse(b"fix: r\xe9parer le parseur", &options).unwrap();
+    /// assert_eq!(decoded.commit().unwrap().description(), "réparer le parseur");
+    ///
+    /// let options = DecodeOptions::new().lossy(true);
+    /// let decoded = Decoded::parse(b"fix: r\xe9parer le parseur", &options).unwrap();
+    /// assert_eq!(decoded.malformed(), [6..7]);
+    /// assert_eq!(decoded.commit().unwrap().description(), "r\u{FFFD}parer le parseur");
+    /// ```
+    pub fn parse(bytes: &'a [u8], options: &DecodeOptions) -> Result<Self, DecodeError> {
+        let label = options.encoding.as_deref().unwrap_or("UTF-8");
+        let encoding =
+            Encoding::for_label(label).ok_or_else(|| DecodeError::UnknownEncoding(label.into()))?;
+        let (text, malformed) = encoding.decode(bytes, options.lossy)?;
+        Ok(Self { text, malformed })
+    }
+
+    /// The decoded commit message.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The byte ranges of the input that were malformed and replaced with `U+FFFD`.
+    pub fn malformed(&self) -> &[Range<usize>] {
+        &self.malformed
+    }
+
+    /// Parse the commit message.
+    pub fn commit(&self) -> Result<Commit<'_>, Error> {
+        Commit::parse(&self.text)
+    }
+
+    /// Parse the commit message with extensions to the specification.
+    pub fn commit_with(&self, options: &ParseOptions) -> Result<Commit<'_>, Error> {
+        Commit::parse_with(&self.text, options)
+    }
+}
+
+/// The error returned when decoding a commit message fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The encoding is not recognized.
+    UnknownEncoding(String),
+
+    /// The message is not valid in its encoding.
+    Malformed {
+        /// The encoding of the message.
+        encoding: &'static str,
+        /// The byte range of the first malformed sequence.
+        bytes: Range<usize>,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownEncoding(label) => write!(f, "Unknown encoding `{label}`"),
+            Self::Malformed { encoding, bytes } => write!(
+                f,
+                "Malformed {encoding} at bytes {}..{}",
+                bytes.start, bytes.end
+            ),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+#[derive(Copy, Clone, Debug)]
+enum Encoding {
+    Utf8,
+    Latin1,
+    #[cfg(feature = "encoding_rs")]
+    Other(&'static encoding_rs::Encoding),
+}
+
+impl Encoding {
+    fn for_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => return Some(Self::Utf8),
+            // WHATWG maps these to windows-1252, which would decode 0x80-0x9F differently
+            "iso-8859-1" | "iso8859-1" | "iso88591" | "iso_8859-1" | "iso_8859-1:1987"
+            | "latin1" | "latin-1" | "l1" | "cp819" | "ibm819" | "iso-ir-100" | "csisolatin1" => {
+                return Some(Self::Latin1);
+            }
+            _ => {}
+        }
+        #[cfg(feature = "encoding_rs")]
+        if let Some(encoding) = encoding_rs::Encoding::for_label(label.as_bytes()) {
+            return Some(if encoding == encoding_rs::UTF_8 {
+                Self::Utf8
+            } else {
+                Self::Other(encoding)
+            });
+        }
+        None
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Latin1 => "ISO-8859-1",
+            #[cfg(feature = "encoding_rs")]
+            Self::Other(encoding) => encoding.name(),
+        }
+    }
+
+    fn decode(
+        self,
+        bytes: &[u8],
+        lossy: bool,
+    ) -> Result<(Cow<'_, str>, Vec<Range<usize>>), DecodeError> {
+        let mut malformed = Vec::new();
+        let mut report = |range: Range<usize>| {
+            if lossy {
+                malformed.push(range);
+                Ok(())
+            } else {
+                Err(DecodeError::Malformed {
+                    encoding: self.name(),
+                    bytes: range,
+                })
+            }
+        };
+
+        let text = match self {
+            Self::Utf8 => match core::str::from_utf8(bytes) {
+                Ok(text) => Cow::Borrowed(text),
+                Err(_) => {
+                    let mut text = String::with_capacity(bytes.len());
+                    let mut offset = 0;
+                    for chunk in bytes.utf8_chunks() {
+                        text.push_str(chunk.valid());
+                        offset += chunk.valid().len();
+                        if !chunk.invalid().is_empty() {
+                            report(offset..offset + chunk.invalid().len())?;
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            offset += chunk.invalid().len();
+                        }
+                    }
+                    Cow::Owned(text)
+                }
+            },
+            Self::Latin1 => Cow::Owned(bytes.iter().map(|b| char::from(*b)).collect()),
+            #[cfg(feature = "encoding_rs")]
+            Self::Other(encoding) => {
+                use encoding_rs::DecoderResult;
+
+                let mut decoder = encoding.new_decoder_without_bom_handling();
+                let mut text = String::with_capacity(bytes.len());
+                let mut read = 0;
+                loop {
+                    let remaining = &bytes[read..];
+                    if let Some(needed) =
+                        decoder.max_utf8_buffer_length_without_replacement(remaining.len())
+                    {
+                        text.reserve(needed);
+                    }
+                    let (result, consumed) =
+                        decoder.decode_to_string_without_replacement(remaining, &mut text, true);
+                    read += consumed;
+                    match result {
+                        DecoderResult::InputEmpty => break,
+                        DecoderResult::OutputFull => {}
+                        DecoderResult::Malformed(len, extra) => {
+                            let end = read - usize::from(extra);
+                            report(end - usize::from(len)..end)?;
+                            text.push(char::REPLACEMENT_CHARACTER);
+                        }
+                    }
+                }
+                Cow::Owned(text)
+            }
+        };
+        Ok((text, malformed))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn test_utf8() {
+        let decoded = Decoded::parse("feat: ✨ sparkle".as_bytes(), &DecodeOptions::new());
+        let decoded = decoded.unwrap();
+        assert!(matches!(decoded.text, Cow::Borrowed(_)));
+        assert_eq!(decoded.commit().unwrap().description(), "✨ sparkle");
+
+        let err = Decoded::parse(b"feat: x\xff\xfey", &DecodeOptions::new()).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::Malformed {
+                encoding: "UTF-8",
+                bytes: 7..8
+            }
+        );
+
+        let options = DecodeOptions::new().lossy(true);
+        let decoded = Decoded::parse(b"x\xff\xfey", &options).unwrap();
+        assert_eq!(decoded.as_str(), "x\u{FFFD}\u{FFFD}y");
+        assert_eq!(decoded.malformed(), [1..2, 2..3]);
+        assert_eq!(decoded.commit().unwrap_err().kind(), ErrorKind::MissingType);
+    }
+
+    #[test]
+    fn test_latin1() {
+        // Unlike windows-1252, 0x80 is a control character rather than `€`
+        let options = DecodeOptions::new().encoding(Some("ISO-8859-1"));
+        let decoded = Decoded::parse(b"fix: \x80 \xe9t\xe9", &options).unwrap();
+        assert_eq!(decoded.as_str(), "fix: \u{80} été");
+
+        let options = DecodeOptions::new().encoding(Some(" Latin1 "));
+        let decoded = Decoded::parse(b"fix: \x80", &options).unwrap();
+        assert_eq!(decoded.as_str(), "fix: \u{80}");
+    }
+
+    #[test]
+    fn test_unknown_encoding() {
+        let options = DecodeOptions::new().encoding(Some("x-unknown"));
+        assert_eq!(
+            Decoded::parse(b"feat: x", &options).unwrap_err(),
+            DecodeError::UnknownEncoding("x-unknown".into())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "encoding_rs")]
+    fn test_encoding_rs() {
+        // "修正" in Shift_JIS
+        let options = DecodeOptions::new().encoding(Some("Shift_JIS"));
+        let decoded = Decoded::parse(b"fix: \x8f\x43\x90\xb3", &options).unwrap();
+        assert_eq!(decoded.commit().unwrap().description(), "修正");
+
+        let err = Decoded::parse(b"fix: \x8f", &options).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::Malformed {
+                encoding: "Shift_JIS",
+                bytes: 5..6
+            }
+        );
+
+        let options = options.lossy(true);
+        let decoded = Decoded::parse(b"fix: \x8f", &options).unwrap();
+        assert_eq!(decoded.as_str(), "fix: \u{FFFD}");
+        assert_eq!(decoded.malformed().len(), 1);
+        assert_eq!(decoded.malformed()[0], 5..6);
+    }
+}
@@ -0,0 +1,511 @@
+//! Editing of commit messages that preserves untouched text.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+
+use crate::commit::NOTES;
+use crate::lines::LinesWithTerminator;
+use crate::parser::footer_start;
+use crate::{Commit, Error, ErrorKind, FooterSeparator, FooterToken, Scope, Type, span};
+
+/// An owned, editable conventional commit message.
+///
+/// Each edit splices only the affected component, leaving the rest of the message byte-for-byte
+/// as it was.  Edits are validated by re-parsing the result; an edit that would leave an invalid
+/// commit is rejected, with the message left unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{CommitBuf, FooterSeparator};
+///
+/// let mut commit = CommitBuf::parse("feat(api):  add endpoint\n\nSome  body.\n\nRefs #1\n").unwrap();
+/// commit.set_type("fix").unwrap();
+/// commit.set_breaking(true).unwrap();
+/// commit.push_footer("Signed-off-by", FooterSeparator::Value, "Lisa <lisa@simpsons.fam>").unwrap();
+///
+/// assert_eq!(
+///     commit.as_str(),
+///     "fix(api)!:  add endpoint\n\nSome  body.\n\nRefs #1\nSigned-off-by: Lisa <lisa@simpsons.fam>\n",
+/// );
+/// assert!(commit.commit().breaking());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CommitBuf {
+    message: String,
+}
+
+impl CommitBuf {
+    /// Take ownership of a commit message.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the commit does not conform to the
+    /// Conventional Commit specification.
+    pub fn parse(message: impl Into<String>) -> Result<Self, Error> {
+        let message = message.into();
+        Commit::parse(&message)?;
+        Ok(Self { message })
+    }
+
+    /// The parsed commit.
+    pub fn commit(&self) -> Commit<'_> {
+        Commit::parse(&self.message).expect("only valid commits are stored")
+    }
+
+    /// The full commit message.
+    pub fn as_str(&self) -> &str {
+        &self.message
+    }
+
+    /// Release the commit message.
+    pub fn into_string(self) -> String {
+        self.message
+    }
+
+    /// Change the commit type.
+    pub fn set_type(&mut self, ty: &str) -> Result<(), Error> {
+        Type::parse(ty)?;
+        let commit = self.commit();
+        let span = self.span(commit.type_().as_str());
+        self.splice(span, ty)
+    }
+
+    /// Change, add, or remove (`None`) the commit scope.
+    pub fn set_scope(&mut self, scope: Option<&str>) -> Result<(), Error> {
+        if let Some(scope) = scope {
+            Scope::parse(scope)?;
+        }
+        let commit = self.commit();
+        let type_end = self.span(commit.type_().as_str()).end;
+        let current = commit
+            .scope()
+            .map(|s| self.span(s.as_str()))
+            // Include the parentheses
+            .map(|s| s.start - 1..s.end + 1)
+            .unwrap_or(type_end..type_end);
+        let replacement = scope.map(|s| format!("({s})")).unwrap_or_default();
+        self.splice(current, &replacement)
+    }
+
+    /// Change the commit description, which must be a single line.
+    pub fn set_description(&mut self, description: &str) -> Result<(), Error> {
+        if description.trim().is_empty() {
+            return Err(Error::new(ErrorKind::MissingDescription));
+        }
+        if description.contains(['\n', '\r']) {
+            return Err(Error::new(ErrorKind::InvalidFormat));
+        }
+        let commit = self.commit();
+        let span = self.span(commit.description());
+        self.splice(span, description)
+    }
+
+    /// Mark the commit as breaking with a `!`, or remove the `!` and any `BREAKING CHANGE`
+    /// footers.
+    pub fn set_breaking(&mut self, breaking: bool) -> Result<(), Error> {
+        let mut edited = self.clone();
+        match (breaking, edited.bang()) {
+            (true, Err(pos)) => edited.splice(pos..pos, "!")?,
+            (false, Ok(pos)) => edited.splice(pos..pos + 1, "")?,
+            (true, Ok(_)) | (false, Err(_)) => {}
+        }
+        if !breaking {
+            while let Some(i) = edited.commit().footers().iter().position(|f| f.breaking()) {
+                edited.remove_footer(i)?;
+            }
+        }
+        *self = edited;
+        Ok(())
+    }
+
+    /// Append a footer after any existing footers.
+    ///
+    /// Later lines of `value` continue it, so they must not start footers of their own.
+    pub fn push_footer(
+        &mut self,
+        token: &str,
+        separator: FooterSeparator,
+        value: &str,
+    ) -> Result<(), Error> {
+        FooterToken::parse(token)?;
+        let footer = format_footer(token, separator, value)?;
+
+        let commit = self.commit();
+        let newline = self.newline();
+        let (end, prefix) = match commit.footers().last() {
+            Some(last) => (self.span(last.value()).end, newline.to_owned()),
+            None => (self.content_end(&commit), newline.repeat(2)),
+        };
+        self.splice(end..end, &format!("{prefix}{footer}"))
+    }
+
+    /// Remove every footer with a matching token, ignoring case, returning how many were removed.
+    pub fn remove_footers(&mut self, token: &str) -> Result<usize, Error> {
+        let mut edited = self.clone();
+        let mut removed = 0;
+        while let Some(i) = edited
+            .commit()
+            .footers()
+            .iter()
+            .position(|f| f.token() == token)
+        {
+            edited.remove_footer(i)?;
+            removed += 1;
+        }
+        *self = edited;
+        Ok(removed)
+    }
+
+    /// Set the value of the first footer with a matching token, ignoring case, removing any
+    /// others with that token.
+    ///
+    /// The footer is appended when there is no matching one.
+    pub fn replace_footer(
+        &mut self,
+        token: &str,
+        separator: FooterSeparator,
+        value: &str,
+    ) -> Result<(), Error> {
+        FooterToken::parse(token)?;
+
+        let commit = self.commit();
+        let mut matching = commit
+            .footers()
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.token() == token)
+            .map(|(i, _)| i);
+        let Some(first) = matching.next() else {
+            return self.push_footer(token, separator, value);
+        };
+        let others: Vec<_> = matching.collect();
+
+        // Keep the existing token casing and, when possible, spacing
+        let existing = commit.footers()[first];
+        let value_span = self.span(existing.value());
+        let replaced = if existing.separator() == separator {
+            check_value(value)?;
+            self.replace(value_span, value)?
+        } else {
+            let token = existing.token().as_str();
+            let footer = format_footer(token, separator, value)?;
+            self.replace(self.span(token).start..value_span.end, &footer)?
+        };
+        let mut edited = Self { message: replaced };
+        for i in others.into_iter().rev() {
+            edited.remove_footer(i)?;
+        }
+        *self = edited;
+        Ok(())
+    }
+
+    fn remove_footer(&mut self, index: usize) -> Result<(), Error> {
+        let commit = self.commit();
+        let footers = commit.footers();
+        let footer = footers[index];
+        let start = self.span(footer.token().as_str()).start;
+        let range = match footers.get(index + 1) {
+            Some(next) => start..self.span(next.token().as_str()).start,
+            None => {
+                let end = self.span(footer.value()).end;
+                let start = match index.checked_sub(1) {
+                    Some(prev) => self.span(footers[prev].value()).end,
+                    None => self.content_end(&commit),
+                };
+                start..end
+            }
+        };
+        self.splice(range, "")
+    }
+
+    /// The position of the `!`, or where it would go.
+    fn bang(&self) -> Result<usize, usize> {
+        let commit = self.commit();
+        let pos = match commit.scope() {
+            Some(scope) => self.span(scope.as_str()).end + 1,
+            None => self.span(commit.type_().as_str()).end,
+        };
+        if self.message[pos..].starts_with('!') {
+            Ok(pos)
+        } else {
+            Err(pos)
+        }
+    }
+
+    /// The byte range of `part`, which must borrow from the message.
+    fn span(&self, part: &str) -> Range<usize> {
+        span(&self.message, part).expect("commits borrow from their message")
+    }
+
+    /// The end of the body, or description when there is no body.
+    fn content_end(&self, commit: &Commit<'_>) -> usize {
+        let content = commit.body().unwrap_or(commit.description());
+        self.span(content).end
+    }
+
+    fn newline(&self) -> &'static str {
+        if self.message.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        }
+    }
+
+    fn replace(&self, range: Range<usize>, replacement: &str) -> Result<String, Error> {
+        let mut message = self.message.clone();
+        message.replace_range(range, replacement);
+        Commit::parse(&message)?;
+        Ok(message)
+    }
+
+    fn splice(&mut self, range: Range<usize>, replacement: &str) -> Result<(), Error> {
+        self.message = self.replace(range, replacement)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for CommitBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl AsRef<str> for CommitBuf {
+    fn as_ref(&self) -> &str {
+        &self.message
+    }
+}
+
+impl FromStr for CommitBuf {
+    type Err = Error;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        Self::parse(message)
+    }
+}
+
+impl From<CommitBuf> for String {
+    fn from(commit: CommitBuf) -> Self {
+        commit.into_string()
+    }
+}
+
+fn format_footer(token: &str, separator: FooterSeparator, value: &str) -> Result<String, Error> {
+    check_value(value)?;
+    let footer = match separator {
+        FooterSeparator::Value => format!("{token}: {value}"),
+        FooterSeparator::Ref => format!("{token} #{value}"),
+    };
+    Ok(footer)
+}
+
+/// Reject empty footer values, and those with lines that would be read as more footers.
+fn check_value(value: &str) -> Result<(), Error> {
+    let mut continuations = LinesWithTerminator::new(value).skip(1);
+    if value.trim().is_empty() || continuations.any(|line| footer_start(&NOTES, line).is_some()) {
+        return Err(Error::new(ErrorKind::InvalidFooter));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_summary_edits() {
+        let mut commit = CommitBuf::parse("Feat:   add thing\n\n  indented body  \n").unwrap();
+
+        commit.set_scope(Some("api")).unwrap();
+        assert_eq!(
+            commit.as_str(),
+            "Feat(api):   add thing\n\n  indented body  \n"
+        );
+
+        commit.set_type("fix").unwrap();
+        commit.set_description("fix thing").unwrap();
+        assert_eq!(
+            commit.as_str(),
+            "fix(api):   fix thing\n\n  indented body  \n"
+        );
+
+        commit.set_breaking(true).unwrap();
+        assert_eq!(
+            commit.as_str(),
+            "fix(api)!:   fix thing\n\n  indented body  \n"
+        );
+
+        commit.set_scope(None).unwrap();
+        assert_eq!(commit.as_str(), "fix!:   fix thing\n\n  indented body  \n");
+
+        commit.set_breaking(false).unwrap();
+        assert_eq!(commit.as_str(), "fix:   fix thing\n\n  indented body  \n");
+    }
+
+    #[test]
+    fn test_invalid_edits() {
+        let original = "feat: thing";
+        let mut commit = CommitBuf::parse(original).unwrap();
+
+        assert!(commit.set_type("a b").is_err());
+        assert!(commit.set_scope(Some("a)b")).is_err());
+        assert!(commit.set_description("").is_err());
+        assert!(commit.set_description("two\nlines").is_err());
+        assert!(
+            commit
+                .push_footer("Refs", FooterSeparator::Value, "")
+                .is_err()
+        );
+        assert!(
+            commit
+                .push_footer("Bad Token", FooterSeparator::Value, "x")
+                .is_err()
+        );
+        assert_eq!(commit.as_str(), original);
+
+        // Removing the footer would leave a stray `\r` after the description
+        let original = "feat!: x\n\nBREAKING CHANGE: y\r";
+        let mut commit = CommitBuf::parse(original).unwrap();
+        assert!(commit.set_breaking(false).is_err());
+        assert!(commit.remove_footers("BREAKING CHANGE").is_err());
+        assert_eq!(commit.as_str(), original);
+        assert!(commit.commit().breaking());
+    }
+
+    #[test]
+    fn test_injected_lines() {
+        let original = "feat: thing\n\nRefs: 1";
+        let mut commit = CommitBuf::parse(original).unwrap();
+
+        let err = commit
+            .set_description("x\n\nBREAKING CHANGE: injected")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFormat);
+        let err = commit
+            .push_footer("Refs", FooterSeparator::Value, "1\nSigned-off-by: Mallory")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFooter);
+        let err = commit
+            .replace_footer("Refs", FooterSeparator::Value, "2\nBREAKING CHANGE: y")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFooter);
+        let err = commit
+            .replace_footer("Refs", FooterSeparator::Ref, "2\r\nCloses #3")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidFooter);
+        assert_eq!(commit.as_str(), original);
+        assert!(!commit.commit().breaking());
+
+        // Continuation lines that are not footers are still allowed
+        commit
+            .push_footer("Note", FooterSeparator::Value, "a\n  b")
+            .unwrap();
+        assert_eq!(commit.commit().footers().len(), 2);
+    }
+
+    #[test]
+    fn test_footer_edits() {
+        let mut commit = CommitBuf::parse("feat: thing").unwrap();
+        commit
+            .push_footer("Refs", FooterSeparator::Ref, "1")
+            .unwrap();
+        commit
+            .push_footer("Signed-off-by", FooterSeparator::Value, "Lisa")
+            .unwrap();
+        assert_eq!(
+            commit.as_str(),
+            "feat: thing\n\nRefs #1\nSigned-off-by: Lisa"
+        );
+
+        commit
+            .replace_footer("refs", FooterSeparator::Value, "#2, #3")
+            .unwrap();
+        assert_eq!(
+            commit.as_str(),
+            "feat: thing\n\nRefs: #2, #3\nSigned-off-by: Lisa"
+        );
+
+        assert_eq!(commit.remove_footers("signed-off-by").unwrap(), 1);
+        assert_eq!(commit.remove_footers("Refs").unwrap(), 1);
+        assert_eq!(commit.as_str(), "feat: thing");
+    }
+
+    #[test]
+    fn test_preserves_untouched_text() {
+        let message = indoc! {"
+            feat:  thing
+
+            Body with  odd   spacing.
+
+
+            Refs:   #1
+            BREAKING CHANGE: a
+              continued value
+            Refs #2
+            Signed-off-by: Bart
+        "};
+        let mut commit = CommitBuf::parse(message).unwrap();
+
+        commit
+            .replace_footer("Refs", FooterSeparator::Value, "#3")
+            .unwrap();
+        assert_eq!(
+            commit.as_str(),
+            indoc! {"
+                feat:  thing
+
+                Body with  odd   spacing.
+
+
+                Refs:   #3
+                BREAKING CHANGE: a
+                  continued value
+                Signed-off-by: Bart
+            "}
+        );
+
+        commit.set_breaking(false).unwrap();
+        assert!(!commit.commit().breaking());
+        assert_eq!(
+            commit.as_str(),
+            indoc! {"
+                feat:  thing
+
+                Body with  odd   spacing.
+
+
+                Refs:   #3
+                Signed-off-by: Bart
+            "}
+        );
+
+        assert_eq!(commit.remove_footers("Signed-off-by").unwrap(), 1);
+        assert_eq!(commit.remove_footers("Refs").unwrap(), 1);
+        assert_eq!(
+            commit.as_str(),
+            "feat:  thing\n\nBody with  odd   spacing.\n"
+        );
+    }
+
+    #[test]
+    fn test_windows_line_endings() {
+        let mut commit = CommitBuf::parse("feat: thing\r\n\r\nbody\r\n").unwrap();
+        commit
+            .push_footer("Refs", FooterSeparator::Ref, "1")
+            .unwrap();
+        commit
+            .push_footer("Refs", FooterSeparator::Ref, "2")
+            .unwrap();
+        assert_eq!(
+            commit.as_str(),
+            "feat: thing\r\n\r\nbody\r\n\r\nRefs #1\r\nRefs #2\r\n"
+        );
+    }
+}
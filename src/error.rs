@@ -1,6 +1,12 @@
 //! All errors related to Conventional Commits.
 
-use std::fmt;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use winnow::error::{AddContext, ParserError, StrContext};
+use winnow::stream::Stream;
 
 /// The error returned when parsing a commit fails.
 pub struct Error {
@@ -8,6 +14,7 @@ pub struct Error {
 
     context: Option<Box<dyn fmt::Display + Send + Sync>>,
     commit: Option<String>,
+    offset: Option<usize>,
 }
 
 impl Error {
@@ -17,6 +24,7 @@ impl Error {
             kind,
             context: None,
             commit: None,
+            offset: None,
         }
     }
 
@@ -24,33 +32,25 @@ impl Error {
         commit: &str,
         err: winnow::error::ParseError<&str, winnow::error::ContextError>,
     ) -> Self {
-        use ErrorKind::{
-            InvalidBody, InvalidFormat, InvalidScope, MissingDescription, MissingType,
-        };
-        use winnow::error::StrContext;
-
-        let mut kind = InvalidFormat;
+        let mut kind = ErrorKind::InvalidFormat;
         for context in err.inner().context() {
-            kind = match context {
-                StrContext::Label(string) => match *string {
-                    crate::parser::SUMMARY => MissingType,
-                    crate::parser::TYPE => MissingType,
-                    crate::parser::SCOPE => InvalidScope,
-                    crate::parser::DESCRIPTION => MissingDescription,
-                    crate::parser::BODY => InvalidBody,
-                    _ => kind,
-                },
-                _ => kind,
-            };
+            kind = label_kind(context).unwrap_or(kind);
         }
 
         Self {
             kind,
             context: None,
             commit: Some(commit.to_owned()),
+            offset: Some(err.offset()),
         }
     }
 
+    pub(crate) fn set_commit(mut self, commit: &str, offset: usize) -> Self {
+        self.commit = Some(commit.to_owned());
+        self.offset = Some(offset);
+        self
+    }
+
     pub(crate) fn set_context(mut self, context: Box<dyn fmt::Display + Send + Sync>) -> Self {
         self.context = Some(context);
         self
@@ -60,6 +60,11 @@ impl Error {
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
+
+    /// The byte offset into the commit message where parsing failed, if known.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
 }
 
 impl fmt::Debug for Error {
@@ -68,6 +73,7 @@ impl fmt::Debug for Error {
             .field("kind", &self.kind)
             .field("context", &self.context.as_ref().map(|s| s.to_string()))
             .field("commit", &self.commit)
+            .field("offset", &self.offset)
             .finish()
     }
 }
@@ -82,14 +88,112 @@ impl fmt::Display for Error {
     }
 }
 
-impl std::error::Error for Error {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         None
     }
 }
 
-/// All possible error kinds returned when parsing a conventional commit.
+/// The error returned when parsing a [`CommitRef`][crate::CommitRef] fails.
+///
+/// Unlike [`Error`], this borrows the commit message rather than copying it.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ErrorRef<'a> {
+    kind: ErrorKind,
+    commit: &'a str,
+    offset: usize,
+}
+
+impl<'a> ErrorRef<'a> {
+    pub(crate) fn new(kind: ErrorKind, commit: &'a str, offset: usize) -> Self {
+        Self {
+            kind,
+            commit,
+            offset,
+        }
+    }
+
+    /// The kind of error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The commit message that failed to parse.
+    pub fn commit(&self) -> &'a str {
+        self.commit
+    }
+
+    /// The byte offset into the commit message where parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for ErrorRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl core::error::Error for ErrorRef<'_> {}
+
+impl From<ErrorRef<'_>> for Error {
+    fn from(err: ErrorRef<'_>) -> Self {
+        Self::new(err.kind).set_commit(err.commit, err.offset)
+    }
+}
+
+/// A parser error that only keeps the [`ErrorKind`] of its most recent label, so failing never
+/// allocates.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct KindError(ErrorKind);
+
+impl KindError {
+    pub(crate) fn kind(&self) -> ErrorKind {
+        self.0
+    }
+}
+
+impl<'a> ParserError<&'a str> for KindError {
+    type Inner = Self;
+
+    fn from_input(_input: &&'a str) -> Self {
+        Self(ErrorKind::InvalidFormat)
+    }
+
+    fn into_inner(self) -> Result<Self::Inner, Self> {
+        Ok(self)
+    }
+}
+
+impl<'a> AddContext<&'a str, StrContext> for KindError {
+    fn add_context(
+        self,
+        _input: &&'a str,
+        _token_start: &<&'a str as Stream>::Checkpoint,
+        context: StrContext,
+    ) -> Self {
+        Self(label_kind(&context).unwrap_or(self.0))
+    }
+}
+
+fn label_kind(context: &StrContext) -> Option<ErrorKind> {
+    let StrContext::Label(label) = context else {
+        return None;
+    };
+    let kind = match *label {
+        crate::parser::SUMMARY => ErrorKind::MissingType,
+        crate::parser::TYPE => ErrorKind::MissingType,
+        crate::parser::SCOPE => ErrorKind::InvalidScope,
+        crate::parser::DESCRIPTION => ErrorKind::MissingDescription,
+        crate::parser::BODY => ErrorKind::InvalidBody,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// All possible error kinds returned when parsing a conventional commit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum ErrorKind {
     /// The commit type is missing from the commit message.
@@ -110,6 +214,24 @@ pub enum ErrorKind {
     /// Any other part of the commit does not conform to the conventional commit
     /// spec.
     InvalidFormat,
+
+    /// The commit type is not one of the accepted types.
+    UnknownType,
+}
+
+impl ErrorKind {
+    /// A stable, machine-readable identifier for this kind of error.
+    pub fn id(self) -> &'static str {
+        match self {
+            ErrorKind::MissingType => "missing-type",
+            ErrorKind::InvalidScope => "invalid-scope",
+            ErrorKind::MissingDescription => "missing-description",
+            ErrorKind::InvalidBody => "invalid-body",
+            ErrorKind::InvalidFooter => "invalid-footer",
+            ErrorKind::InvalidFormat => "invalid-format",
+            ErrorKind::UnknownType => "unknown-type",
+        }
+    }
 }
 
 impl fmt::Display for ErrorKind {
@@ -127,6 +249,7 @@ impl fmt::Display for ErrorKind {
             ErrorKind::InvalidBody => "Incorrect body syntax",
             ErrorKind::InvalidFooter => "Incorrect footer syntax",
             ErrorKind::InvalidFormat => "Incorrect conventional commit format",
+            ErrorKind::UnknownType => "Unknown commit type",
         };
         f.write_str(s)
     }
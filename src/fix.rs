@@ -0,0 +1,290 @@
+//! Repairs for common mistakes in commit summaries.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Commit, TypeRegistry};
+
+/// A corrected commit message.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Fix, Repair};
+///
+/// let fix = Fix::suggest("feat!(api) : add endpoint").unwrap();
+/// assert_eq!(fix.message(), "feat(api)!: add endpoint");
+/// assert_eq!(fix.repairs(), [Repair::ExclamationMarkAfterScope, Repair::SpaceBeforeColon]);
+/// assert_eq!(fix.commit().scope().unwrap(), "api");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fix {
+    message: String,
+    repairs: Vec<Repair>,
+}
+
+impl Fix {
+    /// Try every [`Repair`] against `message`.
+    ///
+    /// Returns `None` when nothing needed repairing or when the repaired message still does not
+    /// parse.
+    pub fn suggest(message: &str) -> Option<Self> {
+        let (summary, rest) = match message.find('\n') {
+            Some(end) => message.split_at(end),
+            None => (message, ""),
+        };
+        let (summary, cr) = match summary.strip_suffix('\r') {
+            Some(summary) => (summary, "\r"),
+            None => (summary, ""),
+        };
+
+        let mut summary = summary.to_owned();
+        let mut repairs = Vec::new();
+        for repair in Repair::SUMMARY {
+            if let Some(repaired) = repair.apply(&summary) {
+                summary = repaired;
+                repairs.push(repair);
+            }
+        }
+
+        let mut rest = rest.to_owned();
+        if let Some(body) = rest.strip_prefix('\n') {
+            if !body
+                .trim_start_matches([' ', '\t'])
+                .starts_with(['\r', '\n'])
+                && !body.is_empty()
+            {
+                rest.insert_str(0, &format!("\n{cr}"));
+                repairs.push(Repair::BlankLineAfterSummary);
+            }
+        }
+
+        if repairs.is_empty() {
+            return None;
+        }
+        let message = format!("{summary}{cr}{rest}");
+        Commit::parse(&message).ok()?;
+        Some(Self { message, repairs })
+    }
+
+    /// The corrected commit message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Release the corrected commit message.
+    pub fn into_message(self) -> String {
+        self.message
+    }
+
+    /// The repairs made, in the order they were applied.
+    pub fn repairs(&self) -> &[Repair] {
+        &self.repairs
+    }
+
+    /// The corrected commit.
+    pub fn commit(&self) -> Commit<'_> {
+        Commit::parse(&self.message).expect("only valid fixes are suggested")
+    }
+}
+
+/// A correction applied by [`Fix::suggest`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Repair {
+    /// `feat!(api): ...` to `feat(api)!: ...`
+    ExclamationMarkAfterScope,
+    /// `fix (api): ...` to `fix(api): ...`
+    SpaceBeforeScope,
+    /// `fix(): ...` to `fix: ...`
+    EmptyScope,
+    /// `fix : ...` to `fix: ...`
+    SpaceBeforeColon,
+    /// `fix foo` to `fix: foo`
+    MissingColon,
+    /// `fix:foo` to `fix: foo`
+    SpaceAfterColon,
+    /// `Fix: ...` to `fix: ...`
+    LowercaseType,
+    /// Separate the body from the summary with a blank line
+    BlankLineAfterSummary,
+}
+
+impl Repair {
+    /// Repairs to the summary, in the order they are tried.
+    const SUMMARY: [Repair; 7] = [
+        Repair::ExclamationMarkAfterScope,
+        Repair::SpaceBeforeScope,
+        Repair::EmptyScope,
+        Repair::SpaceBeforeColon,
+        Repair::MissingColon,
+        Repair::SpaceAfterColon,
+        Repair::LowercaseType,
+    ];
+
+    fn apply(self, summary: &str) -> Option<String> {
+        let ty = &summary[..type_len(summary)];
+        if ty.is_empty() {
+            return None;
+        }
+        let rest = &summary[ty.len()..];
+        let header = header_len(summary);
+        let after_header = &summary[header..];
+
+        match self {
+            Repair::ExclamationMarkAfterScope => {
+                let scoped = rest.strip_prefix("!(")?;
+                let close = scoped.find(')')?;
+                Some(format!(
+                    "{ty}({})!{}",
+                    &scoped[..close],
+                    &scoped[close + 1..]
+                ))
+            }
+            Repair::SpaceBeforeScope => {
+                let scoped = rest.trim_start_matches([' ', '\t']);
+                (is_known_type(ty) && scoped.len() < rest.len() && scoped.starts_with('('))
+                    .then(|| format!("{ty}{scoped}"))
+            }
+            Repair::EmptyScope => rest.strip_prefix("()").map(|rest| format!("{ty}{rest}")),
+            Repair::SpaceBeforeColon => {
+                let colon = after_header.trim_start_matches([' ', '\t']);
+                (colon.len() < after_header.len() && colon.starts_with(':'))
+                    .then(|| format!("{}{colon}", &summary[..header]))
+            }
+            Repair::MissingColon => {
+                let description = after_header.trim_start_matches([' ', '\t']);
+                (is_known_type(ty)
+                    && description.len() < after_header.len()
+                    && !description.is_empty()
+                    && !description.starts_with(':'))
+                .then(|| format!("{}: {description}", &summary[..header]))
+            }
+            Repair::SpaceAfterColon => {
+                let description = after_header.strip_prefix(':')?;
+                (!description.is_empty() && !description.starts_with(char::is_whitespace))
+                    .then(|| format!("{}: {description}", &summary[..header]))
+            }
+            Repair::LowercaseType => (is_known_type(ty) && ty.chars().any(char::is_uppercase))
+                .then(|| format!("{}{rest}", ty.to_lowercase())),
+            Repair::BlankLineAfterSummary => None,
+        }
+    }
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Repair::ExclamationMarkAfterScope => "Moved `!` after the scope",
+            Repair::SpaceBeforeScope => "Removed whitespace between the type and scope",
+            Repair::EmptyScope => "Removed the empty scope",
+            Repair::SpaceBeforeColon => "Removed whitespace before `:`",
+            Repair::MissingColon => "Added `:` after the type",
+            Repair::SpaceAfterColon => "Added a space after `:`",
+            Repair::LowercaseType => "Lowercased the type",
+            Repair::BlankLineAfterSummary => "Added a blank line after the summary",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Whether `ty` is safe to assume when repairing an ambiguous summary.
+fn is_known_type(ty: &str) -> bool {
+    TypeRegistry::conventional()
+        .iter()
+        .any(|info| info.name().eq_ignore_ascii_case(ty))
+}
+
+fn type_len(summary: &str) -> usize {
+    summary
+        .find(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ':' | '!'))
+        .unwrap_or(summary.len())
+}
+
+/// The length of `type(scope)!`
+fn header_len(summary: &str) -> usize {
+    let mut len = type_len(summary);
+    if summary[len..].starts_with('(') {
+        if let Some(close) = summary[len..].find(')') {
+            len += close + 1;
+        }
+    }
+    if summary[len..].starts_with('!') {
+        len += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[track_caller]
+    fn assert_fix(message: &str, expected: &str, repairs: &[Repair]) {
+        let fix = Fix::suggest(message).unwrap();
+        assert_eq!(fix.message(), expected);
+        assert_eq!(fix.repairs(), repairs);
+    }
+
+    #[test]
+    fn test_summary_repairs() {
+        assert_fix("Fix: foo", "fix: foo", &[Repair::LowercaseType]);
+        assert_fix("fix:foo", "fix: foo", &[Repair::SpaceAfterColon]);
+        assert_fix(
+            "fix (api): foo",
+            "fix(api): foo",
+            &[Repair::SpaceBeforeScope],
+        );
+        assert_fix(
+            "feat!(api): foo",
+            "feat(api)!: foo",
+            &[Repair::ExclamationMarkAfterScope],
+        );
+        assert_fix("fix(): foo", "fix: foo", &[Repair::EmptyScope]);
+        assert_fix(
+            "fix(api) : foo",
+            "fix(api): foo",
+            &[Repair::SpaceBeforeColon],
+        );
+        assert_fix("fix foo", "fix: foo", &[Repair::MissingColon]);
+        assert_fix(
+            "FIX (api) foo",
+            "fix(api): foo",
+            &[
+                Repair::SpaceBeforeScope,
+                Repair::MissingColon,
+                Repair::LowercaseType,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_body_repairs() {
+        assert_fix(
+            "fix: foo\nbody\n",
+            "fix: foo\n\nbody\n",
+            &[Repair::BlankLineAfterSummary],
+        );
+        assert_fix(
+            "Fix:foo\r\nbody\r\n",
+            "fix: foo\r\n\r\nbody\r\n",
+            &[
+                Repair::SpaceAfterColon,
+                Repair::LowercaseType,
+                Repair::BlankLineAfterSummary,
+            ],
+        );
+        assert_eq!(Fix::suggest("fix: foo\n\nbody\n"), None);
+    }
+
+    #[test]
+    fn test_no_repair() {
+        assert_eq!(Fix::suggest("fix: foo"), None);
+        assert_eq!(Fix::suggest("Update the readme"), None);
+        assert_eq!(Fix::suggest("Add (experimental) parser"), None);
+        assert_eq!(Fix::suggest(""), None);
+    }
+}
@@ -0,0 +1,264 @@
+//! Typed access to well-known footers.
+
+use core::fmt;
+
+use crate::{Commit, Footer, FooterSeparator};
+
+const CO_AUTHORED_BY: &str = "Co-authored-by";
+const SIGNED_OFF_BY: &str = "Signed-off-by";
+const REVIEWED_BY: &str = "Reviewed-by";
+const REFS: &str = "Refs";
+const CLOSES: &str = "Closes";
+const FIXES: &str = "Fixes";
+
+impl<'a> Commit<'a> {
+    /// The people credited with `Co-authored-by` footers.
+    pub fn co_authors(&self) -> impl Iterator<Item = Identity<'a>> + '_ {
+        self.identities(CO_AUTHORED_BY)
+    }
+
+    /// The people who certified the commit with `Signed-off-by` footers.
+    pub fn signed_off_by(&self) -> impl Iterator<Item = Identity<'a>> + '_ {
+        self.identities(SIGNED_OFF_BY)
+    }
+
+    /// The people who reviewed the commit, according to `Reviewed-by` footers.
+    pub fn reviewed_by(&self) -> impl Iterator<Item = Identity<'a>> + '_ {
+        self.identities(REVIEWED_BY)
+    }
+
+    /// The issues mentioned in `Refs` footers.
+    ///
+    /// Entries that are not issue references are ignored.
+    pub fn refs(&self) -> impl Iterator<Item = IssueRef<'a>> + '_ {
+        self.issues(REFS)
+    }
+
+    /// The issues closed by this commit, according to `Closes` footers.
+    ///
+    /// Entries that are not issue references are ignored.
+    pub fn closes(&self) -> impl Iterator<Item = IssueRef<'a>> + '_ {
+        self.issues(CLOSES)
+    }
+
+    /// The issues fixed by this commit, according to `Fixes` footers.
+    ///
+    /// Entries that are not issue references are ignored.
+    pub fn fixes(&self) -> impl Iterator<Item = IssueRef<'a>> + '_ {
+        self.issues(FIXES)
+    }
+
+    fn identities(&self, token: &'static str) -> impl Iterator<Item = Identity<'a>> + '_ {
+        self.footer_values(token).map(Identity::parse)
+    }
+
+    fn issues(&self, token: &'static str) -> impl Iterator<Item = IssueRef<'a>> + '_ {
+        self.footers()
+            .iter()
+            .filter(move |f| f.token() == token)
+            .flat_map(IssueRef::parse_footer)
+    }
+}
+
+/// A person, as written in footers like `Co-authored-by: Name <email>`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Identity<'a> {
+    name: &'a str,
+    email: Option<&'a str>,
+}
+
+impl<'a> Identity<'a> {
+    /// Piece together an identity.
+    pub const fn new(name: &'a str, email: Option<&'a str>) -> Self {
+        Self { name, email }
+    }
+
+    /// Split a `Name <email>` footer value into its parts.
+    ///
+    /// When there is no `<email>`, the whole value is treated as the name.
+    pub fn parse(value: &'a str) -> Self {
+        let value = value.trim();
+        if let Some(open) = value.rfind('<') {
+            if let Some(email) = value[open + 1..].strip_suffix('>') {
+                return Self::new(value[..open].trim_end(), Some(email.trim()));
+            }
+        }
+        Self::new(value, None)
+    }
+
+    /// The person's name.
+    pub const fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The person's email address, if given.
+    pub const fn email(&self) -> Option<&'a str> {
+        self.email
+    }
+}
+
+impl fmt::Display for Identity<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.email {
+            Some(email) if self.name.is_empty() => write!(f, "<{email}>"),
+            Some(email) => write!(f, "{} <{email}>", self.name),
+            None => f.write_str(self.name),
+        }
+    }
+}
+
+/// A reference to an issue, like `#12` or `owner/repo#34`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IssueRef<'a> {
+    repo: Option<&'a str>,
+    number: u64,
+}
+
+impl<'a> IssueRef<'a> {
+    /// Piece together an issue reference.
+    pub const fn new(repo: Option<&'a str>, number: u64) -> Self {
+        Self { repo, number }
+    }
+
+    /// Parse a single `#12` or `owner/repo#34` reference.
+    pub fn parse(reference: &'a str) -> Option<Self> {
+        let (repo, number) = reference.split_once('#')?;
+        let repo = (!repo.is_empty()).then_some(repo);
+        if repo.is_some_and(|r| r.contains(char::is_whitespace)) {
+            return None;
+        }
+        Some(Self::new(repo, parse_number(number)?))
+    }
+
+    /// Parse every reference in a footer value, like `#12, owner/repo#34`.
+    ///
+    /// With a [`FooterSeparator::Ref`] (`Closes #12`), the leading `#` has already been consumed
+    /// so the value starts with a bare number.
+    fn parse_footer(footer: &Footer<'a>) -> impl Iterator<Item = Self> + 'a {
+        let bare_first = footer.separator() == FooterSeparator::Ref;
+        footer
+            .value()
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(|entry| entry.trim_end_matches(['.', ';']))
+            .filter(|entry| !entry.is_empty())
+            .enumerate()
+            .filter_map(move |(i, entry)| {
+                if bare_first && i == 0 {
+                    if let Some(number) = parse_number(entry) {
+                        return Some(Self::new(None, number));
+                    }
+                }
+                Self::parse(entry)
+            })
+    }
+
+    /// The repository the issue belongs to, when not the current one.
+    pub const fn repo(&self) -> Option<&'a str> {
+        self.repo
+    }
+
+    /// The issue number.
+    pub const fn number(&self) -> u64 {
+        self.number
+    }
+}
+
+impl fmt::Display for IssueRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(repo) = self.repo {
+            f.write_str(repo)?;
+        }
+        write!(f, "#{}", self.number)
+    }
+}
+
+fn parse_number(number: &str) -> Option<u64> {
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use indoc::indoc;
+
+    #[test]
+    fn test_identities() {
+        let commit = Commit::parse(indoc! {"
+            feat: pair on it
+
+            Co-Authored-By: Lisa Simpson <lisa@simpsons.fam>
+            co-authored-by: Bart Simpson
+            Signed-off-by: Marge Simpson <marge@simpsons.fam>
+            Reviewed-by: <homer@simpsons.fam>
+        "})
+        .unwrap();
+
+        assert_eq!(
+            commit.co_authors().collect::<Vec<_>>(),
+            [
+                Identity::new("Lisa Simpson", Some("lisa@simpsons.fam")),
+                Identity::new("Bart Simpson", None),
+            ]
+        );
+        assert_eq!(
+            commit.signed_off_by().collect::<Vec<_>>(),
+            [Identity::new("Marge Simpson", Some("marge@simpsons.fam"))]
+        );
+        let reviewer = commit.reviewed_by().next().unwrap();
+        assert_eq!(reviewer, Identity::new("", Some("homer@simpsons.fam")));
+        assert_eq!(reviewer.to_string(), "<homer@simpsons.fam>");
+    }
+
+    #[test]
+    fn test_issues() {
+        let commit = Commit::parse(indoc! {"
+            fix: all the things
+
+            Closes #12, #13
+            Fixes: owner/repo#34, #35.
+            Refs: #1 and not-an-issue
+            REFS #2
+        "})
+        .unwrap();
+
+        assert_eq!(
+            commit.closes().collect::<Vec<_>>(),
+            [IssueRef::new(None, 12), IssueRef::new(None, 13)]
+        );
+        assert_eq!(
+            commit.fixes().collect::<Vec<_>>(),
+            [
+                IssueRef::new(Some("owner/repo"), 34),
+                IssueRef::new(None, 35)
+            ]
+        );
+        assert_eq!(
+            commit.refs().collect::<Vec<_>>(),
+            [IssueRef::new(None, 1), IssueRef::new(None, 2)]
+        );
+        assert_eq!(
+            IssueRef::new(Some("owner/repo"), 34).to_string(),
+            "owner/repo#34"
+        );
+    }
+
+    #[test]
+    fn test_issue_ref_parse() {
+        assert_eq!(IssueRef::parse("#12"), Some(IssueRef::new(None, 12)));
+        assert_eq!(
+            IssueRef::parse("repo#12"),
+            Some(IssueRef::new(Some("repo"), 12))
+        );
+        assert_eq!(IssueRef::parse("12"), None);
+        assert_eq!(IssueRef::parse("#"), None);
+        assert_eq!(IssueRef::parse("#1a"), None);
+        assert_eq!(IssueRef::parse("#+1"), None);
+    }
+}
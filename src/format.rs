@@ -0,0 +1,335 @@
+//! Normalization of conventional commit messages.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Commit, FooterSeparator};
+
+/// Footer tokens with a well-known casing.
+const CANONICAL_TOKENS: &[&str] = &[
+    "BREAKING CHANGE",
+    "BREAKING-CHANGE",
+    "Acked-by",
+    "Cc",
+    "Closes",
+    "Co-authored-by",
+    "Fixes",
+    "Helped-by",
+    "Refs",
+    "Reported-by",
+    "Resolves",
+    "Reviewed-by",
+    "See-also",
+    "Signed-off-by",
+    "Suggested-by",
+    "Tested-by",
+];
+
+/// Options for [`Commit::format`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    width: Option<usize>,
+}
+
+impl FormatOptions {
+    /// Wrap the body at 72 columns.
+    pub const fn new() -> Self {
+        Self { width: Some(72) }
+    }
+
+    /// Wrap prose in the body to this many columns, or leave line breaks alone with `None`.
+    pub const fn width(mut self, width: Option<usize>) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Commit<'_> {
+    /// Render the commit as a canonical message.
+    ///
+    /// - The type is lowercased and the description trimmed
+    /// - The summary, body, and footers are separated by exactly one blank line
+    /// - Prose in the body is re-wrapped, leaving code blocks, lists, and quotes alone
+    /// - Footers form one contiguous block, with well-known tokens in their canonical casing
+    /// - The message ends with a newline
+    ///
+    /// ```rust
+    /// use git_conventional::{Commit, FormatOptions};
+    ///
+    /// let commit = Commit::parse("FIX(api):  handle timeouts  \n\n\n\nSome context\nwrapped early.\n\nsigned-off-by: Lisa\n\nREFS #1").unwrap();
+    /// assert_eq!(
+    ///     commit.format(&FormatOptions::new()),
+    ///     "fix(api): handle timeouts\n\nSome context wrapped early.\n\nSigned-off-by: Lisa\nRefs #1\n",
+    /// );
+    /// ```
+    pub fn format(&self, options: &FormatOptions) -> String {
+        let mut message = String::new();
+
+        let ticket = self.ticket();
+        if let Some(ticket) = ticket.filter(|_| self.ticket_prefix()) {
+            message.push_str(ticket);
+            message.push(' ');
+        }
+        if let Some(gitmoji) = self.gitmoji() {
+            message.push_str(gitmoji.as_str());
+            message.push(' ');
+        }
+        message.push_str(&self.type_().to_lowercase());
+        if let Some(scope) = self.scope() {
+            message.push('(');
+            message.push_str(scope.trim());
+            message.push(')');
+        }
+        if self.exclamation_mark() {
+            message.push('!');
+        }
+        message.push_str(": ");
+        if let Some(ticket) = ticket.filter(|_| !self.ticket_prefix()) {
+            message.push_str(ticket);
+            message.push(' ');
+        }
+        message.push_str(self.description().trim());
+        message.push('\n');
+
+        if let Some(body) = self.body() {
+            message.push('\n');
+            format_body(&mut message, body, options.width);
+        }
+
+        if !self.footers().is_empty() {
+            message.push('\n');
+            for footer in self.footers() {
+                let token = canonical_token(footer.token().as_str());
+                let separator = match footer.separator() {
+                    FooterSeparator::Value => ": ",
+                    FooterSeparator::Ref => " #",
+                };
+                message.push_str(token);
+                message.push_str(separator);
+                for (i, line) in footer.value().lines().enumerate() {
+                    if 0 < i {
+                        message.push('\n');
+                    }
+                    message.push_str(line.trim_end());
+                }
+                message.push('\n');
+            }
+        }
+
+        message
+    }
+}
+
+fn canonical_token(token: &str) -> &str {
+    CANONICAL_TOKENS
+        .iter()
+        .find(|canonical| canonical.eq_ignore_ascii_case(token))
+        .copied()
+        .unwrap_or(token)
+}
+
+/// Append the body, one paragraph at a time.
+fn format_body(message: &mut String, body: &str, width: Option<usize>) {
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        let line = line.trim_end();
+        // Fences are not prose, so the paragraph containing the code block is kept verbatim
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if line.is_empty() && !in_fence {
+            flush_paragraph(message, &mut paragraph, width);
+            continue;
+        }
+        paragraph.push(line);
+    }
+    flush_paragraph(message, &mut paragraph, width);
+}
+
+fn flush_paragraph(message: &mut String, paragraph: &mut Vec<&str>, width: Option<usize>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    if !message.ends_with("\n\n") {
+        message.push('\n');
+    }
+
+    match width {
+        Some(width) if paragraph.iter().all(|line| is_prose(line)) => {
+            let mut column = 0;
+            for word in paragraph.iter().flat_map(|line| line.split_whitespace()) {
+                let len = word.chars().count();
+                if 0 < column {
+                    if width < column + 1 + len {
+                        message.push('\n');
+                        column = 0;
+                    } else {
+                        message.push(' ');
+                        column += 1;
+                    }
+                }
+                message.push_str(word);
+                column += len;
+            }
+            message.push('\n');
+        }
+        _ => {
+            for line in paragraph.iter() {
+                message.push_str(line);
+                message.push('\n');
+            }
+        }
+    }
+    paragraph.clear();
+}
+
+/// Whether a line is free-flowing text, rather than code, a list, a quote, or a table.
+fn is_prose(line: &str) -> bool {
+    if line.starts_with(char::is_whitespace) {
+        return false;
+    }
+    if line.starts_with(['>', '|', '#', '`', '~']) {
+        return false;
+    }
+    if ["- ", "* ", "+ "]
+        .iter()
+        .any(|marker| line.starts_with(marker))
+    {
+        return false;
+    }
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    if 0 < digits && (line[digits..].starts_with(". ") || line[digits..].starts_with(") ")) {
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_summary() {
+        let commit = Commit::parse("Feat(Scope)!:   Add thing   ").unwrap();
+        assert_eq!(
+            commit.format(&FormatOptions::new()),
+            "feat(Scope)!: Add thing\n"
+        );
+    }
+
+    #[test]
+    fn test_extensions() {
+        let options = crate::ParseOptions::new()
+            .gitmoji(true)
+            .ticket(crate::TicketPattern::new().projects(["PROJ"]));
+        let commit = Commit::parse_with("[PROJ-1]  :bug:  Fix: x", &options).unwrap();
+        assert_eq!(
+            commit.format(&FormatOptions::new()),
+            "PROJ-1 :bug: fix: x\n"
+        );
+        let commit = Commit::parse_with("fix: PROJ-1: x", &options).unwrap();
+        assert_eq!(commit.format(&FormatOptions::new()), "fix: PROJ-1 x\n");
+    }
+
+    #[test]
+    fn test_wrap() {
+        let commit = Commit::parse(indoc! {"
+            docs: explain wrapping
+
+            This paragraph is
+            wrapped too early, and then it goes on for much too long to fit within the limit.
+
+
+
+            - lists are
+              left alone
+            1. even numbered ones
+
+                indented code
+                is left alone
+
+            ```
+            fenced code
+
+            is left alone
+            ```
+            A https://example.com/a-really-long-url-that-cannot-be-broken-up-at-all
+        "})
+        .unwrap();
+
+        assert_eq!(
+            commit.format(&FormatOptions::new().width(Some(30))),
+            indoc! {"
+                docs: explain wrapping
+
+                This paragraph is wrapped too
+                early, and then it goes on for
+                much too long to fit within
+                the limit.
+
+                - lists are
+                  left alone
+                1. even numbered ones
+
+                    indented code
+                    is left alone
+
+                ```
+                fenced code
+
+                is left alone
+                ```
+                A https://example.com/a-really-long-url-that-cannot-be-broken-up-at-all
+            "}
+        );
+    }
+
+    #[test]
+    fn test_no_wrap() {
+        let commit = Commit::parse("docs: x\n\nshort\nlines").unwrap();
+        assert_eq!(
+            commit.format(&FormatOptions::new().width(None)),
+            "docs: x\n\nshort\nlines\n"
+        );
+    }
+
+    #[test]
+    fn test_footers() {
+        let commit = Commit::parse(indoc! {"
+            fix: x
+
+            body
+
+            co-authored-by: Lisa
+            breaking-change: it broke
+              in two lines
+
+
+            X-Custom-Token: kept
+            closes #1
+        "})
+        .unwrap();
+
+        assert_eq!(
+            commit.format(&FormatOptions::new()),
+            indoc! {"
+                fix: x
+
+                body
+
+                Co-authored-by: Lisa
+                BREAKING-CHANGE: it broke
+                  in two lines
+                X-Custom-Token: kept
+                Closes #1
+            "}
+        );
+    }
+}
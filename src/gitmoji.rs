@@ -0,0 +1,131 @@
+//! [gitmoji](https://gitmoji.dev) prefixes, like `✨ feat: ...` or `:bug: fix: ...`.
+
+use core::fmt;
+
+use crate::Type;
+
+/// Well-known gitmojis: the emoji, its shortcode, and the type it implies.
+const GITMOJIS: &[(&str, &str, Option<Type<'static>>)] = &[
+    ("✨", "sparkles", Some(Type::FEAT)),
+    ("🐛", "bug", Some(Type::FIX)),
+    ("🚑", "ambulance", Some(Type::FIX)),
+    ("🩹", "adhesive_bandage", Some(Type::FIX)),
+    ("🔒", "lock", Some(Type::FIX)),
+    ("📝", "memo", Some(Type::DOCS)),
+    ("💡", "bulb", Some(Type::DOCS)),
+    ("🎨", "art", Some(Type::STYLE)),
+    ("🚨", "rotating_light", Some(Type::STYLE)),
+    ("♻", "recycle", Some(Type::REFACTOR)),
+    ("🚚", "truck", Some(Type::REFACTOR)),
+    ("⚡", "zap", Some(Type::PERF)),
+    ("✅", "white_check_mark", Some(Type::TEST)),
+    ("🧪", "test_tube", Some(Type::TEST)),
+    ("🔧", "wrench", Some(Type::CHORE)),
+    ("🔨", "hammer", Some(Type::CHORE)),
+    ("🔖", "bookmark", Some(Type::CHORE)),
+    ("👷", "construction_worker", Some(Type::new_unchecked("ci"))),
+    ("💚", "green_heart", Some(Type::new_unchecked("ci"))),
+    ("📦", "package", Some(Type::new_unchecked("build"))),
+    ("⬆", "arrow_up", Some(Type::new_unchecked("build"))),
+    ("⬇", "arrow_down", Some(Type::new_unchecked("build"))),
+    ("📌", "pushpin", Some(Type::new_unchecked("build"))),
+    ("➕", "heavy_plus_sign", Some(Type::new_unchecked("build"))),
+    ("➖", "heavy_minus_sign", Some(Type::new_unchecked("build"))),
+    ("⏪", "rewind", Some(Type::REVERT)),
+    ("🔥", "fire", None),
+    ("💥", "boom", None),
+    ("🚀", "rocket", None),
+    ("🎉", "tada", None),
+    ("🚧", "construction", None),
+    ("💄", "lipstick", None),
+    ("✏", "pencil2", None),
+    ("🏷", "label", None),
+    ("🌐", "globe_with_meridians", None),
+    ("🔀", "twisted_rightwards_arrows", None),
+    ("🗑", "wastebasket", None),
+];
+
+/// A gitmoji leading the commit summary, either as unicode or a `:shortcode:`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Gitmoji<'a>(&'a str);
+
+impl<'a> Gitmoji<'a> {
+    pub(crate) fn new_unchecked(gitmoji: &'a str) -> Self {
+        Self(gitmoji)
+    }
+
+    /// The gitmoji, as written.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// The unicode emoji, for well-known gitmojis.
+    pub fn emoji(&self) -> Option<&'static str> {
+        self.known().map(|(emoji, _, _)| *emoji)
+    }
+
+    /// The shortcode, without colons, for well-known gitmojis.
+    pub fn shortcode(&self) -> Option<&'static str> {
+        self.known().map(|(_, shortcode, _)| *shortcode)
+    }
+
+    /// The conventional type the gitmoji stands for, like `feat` for `✨`.
+    pub fn implied_type(&self) -> Option<Type<'static>> {
+        self.known().and_then(|(_, _, ty)| *ty)
+    }
+
+    fn known(&self) -> Option<&'static (&'static str, &'static str, Option<Type<'static>>)> {
+        match self.0.strip_prefix(':').and_then(|s| s.strip_suffix(':')) {
+            Some(shortcode) => GITMOJIS
+                .iter()
+                .find(|(_, known, _)| known.eq_ignore_ascii_case(shortcode)),
+            None => {
+                // Ignore variation selectors, which are optional in practice
+                let emoji = self.0.trim_end_matches('\u{FE0F}');
+                GITMOJIS.iter().find(|(known, _, _)| *known == emoji)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Gitmoji<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl PartialEq<&str> for Gitmoji<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_known() {
+        let sparkles = Gitmoji::new_unchecked("✨");
+        assert_eq!(sparkles.shortcode(), Some("sparkles"));
+        assert_eq!(sparkles.implied_type(), Some(Type::FEAT));
+
+        let zap = Gitmoji::new_unchecked("⚡\u{FE0F}");
+        assert_eq!(zap.emoji(), Some("⚡"));
+        assert_eq!(zap.implied_type(), Some(Type::PERF));
+
+        let bug = Gitmoji::new_unchecked(":bug:");
+        assert_eq!(bug.emoji(), Some("🐛"));
+        assert_eq!(bug.implied_type(), Some(Type::FIX));
+
+        let rocket = Gitmoji::new_unchecked(":rocket:");
+        assert_eq!(rocket.emoji(), Some("🚀"));
+        assert_eq!(rocket.implied_type(), None);
+
+        let unknown = Gitmoji::new_unchecked(":unknown:");
+        assert_eq!(unknown.emoji(), None);
+        assert_eq!(unknown.implied_type(), None);
+    }
+}
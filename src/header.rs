@@ -0,0 +1,178 @@
+//! Summary headers described by a regular expression, like conventional-changelog's
+//! `headerPattern` and `headerCorrespondence`.
+
+use core::fmt;
+
+use regex::Regex;
+
+use crate::{Error, ErrorKind};
+
+/// A regular expression for the summary line, for [`ParseOptions::header`].
+///
+/// The parts of the header are found through capture groups, either named `type`, `scope`,
+/// `subject`, and `breaking`, or numbered and listed with [`HeaderPattern::correspondence`].  A
+/// non-empty `breaking` group marks the commit as breaking, like `!` does.
+///
+/// [`ParseOptions::header`]: crate::ParseOptions::header
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Commit, HeaderPattern, ParseOptions};
+///
+/// // A preset with `noteKeywords: ["BREAKING CHANGES"]` and the header `[type] subject`
+/// let header = HeaderPattern::new(r"^\[(\w+)\] (.+)$")
+///     .unwrap()
+///     .correspondence(["type", "subject"]);
+/// let options = ParseOptions::new()
+///     .header(header)
+///     .note_keywords(["BREAKING CHANGES"]);
+///
+/// let message = "[feat] add dark mode\n\nBREAKING CHANGES: themes are renamed";
+/// let commit = Commit::parse_with(message, &options).unwrap();
+/// assert_eq!(commit.type_(), "feat");
+/// assert_eq!(commit.description(), "add dark mode");
+/// assert_eq!(commit.breaking_description(), Some("themes are renamed"));
+/// ```
+#[derive(Clone)]
+pub struct HeaderPattern {
+    regex: Regex,
+    ty: Option<usize>,
+    scope: Option<usize>,
+    subject: Option<usize>,
+    breaking: Option<usize>,
+}
+
+impl HeaderPattern {
+    /// Match the summary line against `pattern`, using its named capture groups.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        let regex = Regex::new(pattern)?;
+        let group = |name| regex.capture_names().position(|group| group == Some(name));
+        Ok(Self {
+            ty: group("type"),
+            scope: group("scope"),
+            subject: group("subject").or_else(|| group("description")),
+            breaking: group("breaking"),
+            regex,
+        })
+    }
+
+    /// The default header of the conventional-changelog presets,
+    /// `^(\w*)(?:\((.*)\))?(!)?: (.*)$`.
+    pub fn conventional() -> Self {
+        Self::new(r"^(\w*)(?:\((.*)\))?(!)?: (.*)$")
+            .expect("valid pattern")
+            .correspondence(["type", "scope", "breaking", "subject"])
+    }
+
+    /// Name the numbered capture groups, in order, e.g. `["type", "scope", "subject"]`.
+    ///
+    /// Unknown names are ignored.
+    pub fn correspondence<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for (i, name) in names.into_iter().enumerate() {
+            let group = Some(i + 1);
+            match name.as_ref() {
+                "type" => self.ty = group,
+                "scope" => self.scope = group,
+                "subject" | "description" => self.subject = group,
+                "breaking" => self.breaking = group,
+                _ => {}
+            }
+        }
+        self
+    }
+
+    /// The regular expression.
+    pub fn as_str(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// Split `summary` into its type, scope, breaking flag, and description.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn parse<'a>(
+        &self,
+        summary: &'a str,
+    ) -> Result<(&'a str, Option<&'a str>, bool, &'a str), Error> {
+        let captures = self
+            .regex
+            .captures(summary)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidFormat))?;
+        let group = |index: Option<usize>| {
+            index
+                .and_then(|i| captures.get(i))
+                .map(|m| m.as_str())
+                .filter(|s| !s.is_empty())
+        };
+
+        let ty = group(self.ty).ok_or_else(|| Error::new(ErrorKind::MissingType))?;
+        let scope = group(self.scope);
+        let breaking = group(self.breaking).is_some();
+        let description =
+            group(self.subject).ok_or_else(|| Error::new(ErrorKind::MissingDescription))?;
+        Ok((ty, scope, breaking, description))
+    }
+}
+
+impl fmt::Debug for HeaderPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeaderPattern")
+            .field("regex", &self.regex.as_str())
+            .field("type", &self.ty)
+            .field("scope", &self.scope)
+            .field("subject", &self.subject)
+            .field("breaking", &self.breaking)
+            .finish()
+    }
+}
+
+impl PartialEq for HeaderPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+            && self.ty == other.ty
+            && self.scope == other.scope
+            && self.subject == other.subject
+            && self.breaking == other.breaking
+    }
+}
+
+impl Eq for HeaderPattern {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_conventional() {
+        let header = HeaderPattern::conventional();
+        assert_eq!(
+            header.parse("feat(ui)!: add dark mode").unwrap(),
+            ("feat", Some("ui"), true, "add dark mode")
+        );
+        assert_eq!(
+            header.parse("fix: handle EOF").unwrap(),
+            ("fix", None, false, "handle EOF")
+        );
+        assert_eq!(
+            header.parse("handle EOF").unwrap_err().kind(),
+            ErrorKind::InvalidFormat
+        );
+        assert_eq!(
+            header.parse(": handle EOF").unwrap_err().kind(),
+            ErrorKind::MissingType
+        );
+    }
+
+    #[test]
+    fn test_named_groups() {
+        let header =
+            HeaderPattern::new(r"^(?<subject>.+) \((?<type>\w+)(?:/(?<scope>\w+))?\)$").unwrap();
+        assert_eq!(
+            header.parse("add dark mode (feat/ui)").unwrap(),
+            ("feat", Some("ui"), false, "add dark mode")
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! Classifying the parts of commit messages, for syntax highlighting.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::ReferenceExtractor;
+use crate::commit::NOTES;
+use crate::lines::LinesWithTerminator;
+use crate::parser::{footer_start, scope, type_};
+
+/// Classifies the parts of commit messages, like for syntax highlighting.
+///
+/// This follows the grammar of [`Commit::parse`] but never fails.  Text the grammar does not
+/// accept is classified as [`TokenKind::Invalid`], with the rest of the message classified as
+/// far as possible, so messages can be highlighted as they are typed.
+///
+/// [`Commit::parse`]: crate::Commit::parse
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Highlighter, TokenKind};
+///
+/// let message = "feat(api)!: add endpoint for #12\n\nCloses #13";
+/// let tokens = Highlighter::new().tokens(message);
+/// let tokens: Vec<_> = tokens.iter().map(|t| (t.kind(), &message[t.range()])).collect();
+/// assert_eq!(tokens, [
+///     (TokenKind::Type, "feat"),
+///     (TokenKind::Punctuation, "("),
+///     (TokenKind::Scope, "api"),
+///     (TokenKind::Punctuation, ")"),
+///     (TokenKind::Breaking, "!"),
+///     (TokenKind::Punctuation, ":"),
+///     (TokenKind::Description, "add endpoint for "),
+///     (TokenKind::IssueRef, "#12"),
+///     (TokenKind::FooterToken, "Closes"),
+///     (TokenKind::FooterSeparator, "#"),
+///     (TokenKind::IssueRef, "13"),
+/// ]);
+///
+/// let message = "fix(api) handle EOF\n\nBody";
+/// let tokens = Highlighter::new().tokens(message);
+/// let tokens: Vec<_> = tokens.iter().map(|t| (t.kind(), &message[t.range()])).collect();
+/// assert_eq!(tokens[4], (TokenKind::Invalid, " handle EOF"));
+/// assert_eq!(tokens[5], (TokenKind::Body, "Body"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Highlighter {
+    references: ReferenceExtractor,
+}
+
+impl Highlighter {
+    /// Highlight issue and merge request references, like `#12`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highlight the references found by `references`, like Jira keys.
+    pub fn references(mut self, references: ReferenceExtractor) -> Self {
+        self.references = references;
+        self
+    }
+
+    /// Classify the parts of `message`.
+    ///
+    /// Tokens are in order and do not overlap.  Whitespace, line endings, and blank lines
+    /// between the parts are left out.
+    pub fn tokens(&self, message: &str) -> Vec<SemanticToken> {
+        let mut tokens = Tokens {
+            tokens: Vec::new(),
+            references: &self.references,
+            message,
+        };
+        let summary_end = message.find('\n').map(|i| i + 1).unwrap_or(message.len());
+        tokens.summary(&message[..summary_end]);
+        tokens.trailer(summary_end);
+        tokens.tokens
+    }
+
+    /// Render `message` for a terminal, colored with ANSI escape codes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use git_conventional::Highlighter;
+    ///
+    /// let rendered = Highlighter::new().ansi("fix: handle EOF").to_string();
+    /// assert_eq!(rendered, "\x1b[1;34mfix\x1b[0m\x1b[2m:\x1b[0m handle EOF");
+    /// ```
+    pub fn ansi<'a>(&self, message: &'a str) -> Ansi<'a> {
+        Ansi {
+            message,
+            tokens: self.tokens(message),
+        }
+    }
+}
+
+/// A classified part of a commit message, from [`Highlighter::tokens`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SemanticToken {
+    kind: TokenKind,
+    range: Range<usize>,
+}
+
+impl SemanticToken {
+    /// What this part of the message is.
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// The byte range of this part of the message.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// The kinds of [`SemanticToken`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenKind {
+    /// The type of the commit, like `feat`
+    Type,
+    /// The scope of the commit, without its parentheses
+    Scope,
+    /// The `!` marking a breaking change
+    Breaking,
+    /// The parentheses around the scope and the `:` before the description
+    Punctuation,
+    /// The description of the commit
+    Description,
+    /// The body of the commit
+    Body,
+    /// The token of a footer, like `Refs`
+    FooterToken,
+    /// The `:` or `#` between a footer's token and value
+    FooterSeparator,
+    /// The value of a footer
+    FooterValue,
+    /// A reference to an issue, merge request, or ticket, like `#12`
+    IssueRef,
+    /// Text that does not conform to the Conventional Commit specification
+    Invalid,
+}
+
+impl TokenKind {
+    /// The SGR parameters for rendering this kind, if any.
+    fn style(self) -> Option<&'static str> {
+        match self {
+            TokenKind::Type => Some("1;34"),
+            TokenKind::Scope => Some("36"),
+            TokenKind::Breaking => Some("1;31"),
+            TokenKind::Punctuation | TokenKind::FooterSeparator => Some("2"),
+            TokenKind::FooterToken => Some("33"),
+            TokenKind::IssueRef => Some("35"),
+            TokenKind::Invalid => Some("4;31"),
+            TokenKind::Description | TokenKind::Body | TokenKind::FooterValue => None,
+        }
+    }
+}
+
+/// A commit message colored with ANSI escape codes, from [`Highlighter::ansi`].
+///
+/// Styles are reset at the end of each line, so the output can be paged or split into lines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ansi<'a> {
+    message: &'a str,
+    tokens: Vec<SemanticToken>,
+}
+
+impl fmt::Display for Ansi<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut end = 0;
+        for token in &self.tokens {
+            f.write_str(&self.message[end..token.range.start])?;
+            let text = &self.message[token.range()];
+            match token.kind.style() {
+                Some(style) => {
+                    for (i, line) in text.split('\n').enumerate() {
+                        if 0 < i {
+                            f.write_str("\n")?;
+                        }
+                        let (line, cr) = match line.strip_suffix('\r') {
+                            Some(line) => (line, "\r"),
+                            None => (line, ""),
+                        };
+                        if !line.is_empty() {
+                            write!(f, "\x1b[{style}m{line}\x1b[0m")?;
+                        }
+                        f.write_str(cr)?;
+                    }
+                }
+                None => f.write_str(text)?,
+            }
+            end = token.range.end;
+        }
+        f.write_str(&self.message[end..])
+    }
+}
+
+struct Tokens<'h, 'm> {
+    tokens: Vec<SemanticToken>,
+    references: &'h ReferenceExtractor,
+    message: &'m str,
+}
+
+impl Tokens<'_, '_> {
+    fn push(&mut self, kind: TokenKind, range: Range<usize>) {
+        if !range.is_empty() {
+            self.tokens.push(SemanticToken { kind, range });
+        }
+    }
+
+    /// Push free-form text, splitting out any references.
+    fn push_text(&mut self, kind: TokenKind, range: Range<usize>, after_hash: bool) {
+        let mut start = range.start;
+        for span in self
+            .references
+            .spans(&self.message[range.clone()], after_hash)
+        {
+            let span = range.start + span.start..range.start + span.end;
+            self.push(kind, start..span.start);
+            start = span.end;
+            self.push(TokenKind::IssueRef, span);
+        }
+        self.push(kind, start..range.end);
+    }
+
+    // <summary>         ::= <type>, "(", <scope>, ")", ["!"], ":", <whitespace>*, <text>
+    //                    |  <type>, ["!"], ":", <whitespace>*, <text>
+    fn summary(&mut self, line: &str) {
+        let line = line.trim_end_matches(['\n', '\r']);
+        let at = |rest: &str| line.len() - rest.len();
+        let mut rest = line;
+
+        // Only accept the type when followed by the rest of a summary, to not mistake the first
+        // word of any message as its type
+        let ty = type_::<()>(&mut rest).ok();
+        if ty.is_none() || !(rest.is_empty() || rest.starts_with(['(', '!', ':'])) {
+            self.push(TokenKind::Invalid, 0..line.len());
+            return;
+        }
+        self.push(TokenKind::Type, 0..at(rest));
+
+        if let Some(scoped) = rest.strip_prefix('(') {
+            self.push(TokenKind::Punctuation, at(rest)..at(scoped));
+            rest = scoped;
+            if scope::<()>(&mut rest).is_ok() {
+                self.push(TokenKind::Scope, at(scoped)..at(rest));
+            }
+            match rest.strip_prefix(')') {
+                Some(closed) => {
+                    self.push(TokenKind::Punctuation, at(rest)..at(closed));
+                    rest = closed;
+                }
+                None => {
+                    self.push(TokenKind::Invalid, at(rest)..line.len());
+                    return;
+                }
+            }
+        }
+
+        if let Some(after) = rest.strip_prefix('!') {
+            self.push(TokenKind::Breaking, at(rest)..at(after));
+            rest = after;
+        }
+
+        match rest.strip_prefix(':') {
+            Some(description) => {
+                self.push(TokenKind::Punctuation, at(rest)..at(description));
+                let description = description.trim_start();
+                self.push_text(TokenKind::Description, at(description)..line.len(), false);
+            }
+            None => self.push(TokenKind::Invalid, at(rest)..line.len()),
+        }
+    }
+
+    // <trailer>         ::= <newline>+, <body>, (<newline>+, <footer>)*
+    //                    |  (<newline>+, <footer>)*
+    //                    |  <newline>*
+    //
+    // Like `parser::body` and `parser::value`, footers start after a blank line and run until the
+    // next footer.  A value may start on a later line, which is never a footer itself.
+    fn trailer(&mut self, start: usize) {
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        enum State {
+            /// The body must begin one blank line after the summary
+            Unseparated,
+            Body,
+            Footers,
+        }
+
+        let mut state = State::Unseparated;
+        let mut prior_is_empty = false;
+        // Whether the last footer's value has yet to start
+        let mut value_pending = false;
+        // The free-form text being accumulated, and whether it follows a `#` separator
+        let mut text: Option<(TokenKind, Range<usize>, bool)> = None;
+        let mut offset = start;
+        for line in LinesWithTerminator::new(&self.message[start..]) {
+            let line_start = offset;
+            offset += line.len();
+            let content = line.trim_end();
+            if content.trim_start().is_empty() {
+                prior_is_empty = true;
+                if state == State::Unseparated {
+                    state = State::Body;
+                }
+                continue;
+            }
+            let content_end = line_start + content.len();
+
+            let footer = match state {
+                _ if value_pending => None,
+                State::Unseparated => None,
+                State::Body if !prior_is_empty => None,
+                State::Body | State::Footers => footer_start(&NOTES, line),
+            };
+            prior_is_empty = false;
+            if let Some((token, separator)) = footer {
+                state = State::Footers;
+                self.flush(text.take());
+                let token_end = line_start + token.len();
+                self.push(TokenKind::FooterToken, line_start..token_end);
+                let hash = separator == " #";
+                let separator_end = token_end + separator.len();
+                self.push(
+                    TokenKind::FooterSeparator,
+                    separator_end - separator.trim_start().len()..separator_end,
+                );
+                let value = content[token.len() + separator.len()..].trim_start();
+                let value_start = content_end - value.len();
+                value_pending = value.is_empty();
+                text = Some((TokenKind::FooterValue, value_start..content_end, hash));
+                continue;
+            }
+            if value_pending {
+                value_pending = false;
+                let hash = text.is_some_and(|(_, _, hash)| hash);
+                text = Some((TokenKind::FooterValue, line_start..content_end, hash));
+                continue;
+            }
+
+            let kind = match state {
+                State::Unseparated => TokenKind::Invalid,
+                State::Body => TokenKind::Body,
+                State::Footers => TokenKind::FooterValue,
+            };
+            text = match text {
+                Some((current, range, hash)) if current == kind => {
+                    Some((kind, range.start..content_end, hash))
+                }
+                current => {
+                    self.flush(current);
+                    Some((kind, line_start..content_end, false))
+                }
+            };
+        }
+        self.flush(text);
+    }
+
+    fn flush(&mut self, text: Option<(TokenKind, Range<usize>, bool)>) {
+        match text {
+            Some((TokenKind::Invalid, range, _)) => self.push(TokenKind::Invalid, range),
+            Some((kind, range, hash)) => self.push_text(kind, range, hash),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn tokens(message: &str) -> Vec<(TokenKind, &str)> {
+        Highlighter::new()
+            .tokens(message)
+            .into_iter()
+            .map(|t| (t.kind(), &message[t.range()]))
+            .collect()
+    }
+
+    #[test]
+    fn test_trailer() {
+        let message = "fix: handle EOF\r\n\r\nFirst paragraph\r\nwith #1.\r\n\r\nSecond paragraph\
+                       \r\n\r\nRefs: #2\r\nCo-authored-by: Lisa\r\n  continued\r\n\r\nBREAKING CHANGE: x\r\n";
+        assert_eq!(
+            tokens(message),
+            [
+                (TokenKind::Type, "fix"),
+                (TokenKind::Punctuation, ":"),
+                (TokenKind::Description, "handle EOF"),
+                (TokenKind::Body, "First paragraph\r\nwith "),
+                (TokenKind::IssueRef, "#1"),
+                (TokenKind::Body, ".\r\n\r\nSecond paragraph"),
+                (TokenKind::FooterToken, "Refs"),
+                (TokenKind::FooterSeparator, ":"),
+                (TokenKind::IssueRef, "#2"),
+                (TokenKind::FooterToken, "Co-authored-by"),
+                (TokenKind::FooterSeparator, ":"),
+                (TokenKind::FooterValue, "Lisa\r\n  continued"),
+                (TokenKind::FooterToken, "BREAKING CHANGE"),
+                (TokenKind::FooterSeparator, ":"),
+                (TokenKind::FooterValue, "x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_commit() {
+        let messages = [
+            "feat(ui)!: add dark mode\n\nBody\n\nwith paragraphs\n\nRefs: #1\nCloses #2",
+            "fix: handle EOF\n\nBREAKING-CHANGE: first\n  continued\nBREAKING CHANGE: second\n\n",
+            "fix: handle EOF\n\nNot: a footer\nbecause of this line\n\nAcked-by: Bart",
+            "chore: release\r\n\r\nSigned-off-by: Lisa\r\n",
+            "fix: x\n\nNote:\nsee #1",
+            "fix: x\n\nRefs:\n\n  Closes #2\nFixes #3",
+        ];
+        for message in messages {
+            let commit = crate::Commit::parse(message).unwrap();
+            let tokens = Highlighter::new().tokens(message);
+            let of = |kind| {
+                tokens
+                    .iter()
+                    .filter(move |t| t.kind() == kind)
+                    .map(|t| &message[t.range()])
+            };
+            assert_eq!(of(TokenKind::Type).next(), Some(commit.type_().as_str()));
+            assert_eq!(
+                of(TokenKind::Scope).next(),
+                commit.scope().map(|s| s.as_str())
+            );
+            assert_eq!(of(TokenKind::Body).next(), commit.body(), "{message:?}");
+            let footers: Vec<_> = commit
+                .footers()
+                .iter()
+                .map(|f| f.token().as_str())
+                .collect();
+            assert!(of(TokenKind::FooterToken).eq(footers), "{message:?}");
+            assert!(!tokens.iter().any(|t| t.kind() == TokenKind::Invalid));
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(tokens(""), []);
+        assert_eq!(
+            tokens("Update readme"),
+            [(TokenKind::Invalid, "Update readme")]
+        );
+        assert_eq!(
+            tokens("feat(api: add"),
+            [
+                (TokenKind::Type, "feat"),
+                (TokenKind::Punctuation, "("),
+                (TokenKind::Scope, "api: add"),
+            ]
+        );
+        assert_eq!(
+            tokens("feat!(api): add\nbody\nmore\n\nRefs: #1"),
+            [
+                (TokenKind::Type, "feat"),
+                (TokenKind::Breaking, "!"),
+                (TokenKind::Invalid, "(api): add"),
+                (TokenKind::Invalid, "body\nmore"),
+                (TokenKind::FooterToken, "Refs"),
+                (TokenKind::FooterSeparator, ":"),
+                (TokenKind::IssueRef, "#1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ansi() {
+        let rendered = Highlighter::new()
+            .ansi("feat(ui): x\n\nRefs: #1\n  and #2\n")
+            .to_string();
+        assert_eq!(
+            rendered,
+            "\x1b[1;34mfeat\x1b[0m\x1b[2m(\x1b[0m\x1b[36mui\x1b[0m\x1b[2m)\x1b[0m\x1b[2m:\x1b[0m x\n\
+             \n\
+             \x1b[33mRefs\x1b[0m\x1b[2m:\x1b[0m \x1b[35m#1\x1b[0m\n  and \x1b[35m#2\x1b[0m\n"
+        );
+
+        // A lone word may be a type still being typed
+        let rendered = Highlighter::new().ansi("x\r\ny").to_string();
+        assert_eq!(rendered, "\x1b[1;34mx\x1b[0m\r\n\x1b[4;31my\x1b[0m");
+    }
+}
@@ -0,0 +1,562 @@
+//! Re-parsing commit messages as they are edited.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use winnow::Parser;
+use winnow::ascii::line_ending;
+use winnow::combinator::repeat;
+
+use crate::commit::NOTES;
+use crate::error::KindError;
+use crate::parser::{body, footer, summary, trailer};
+use crate::{
+    Commit, Error, ErrorKind, ErrorRef, Footer, FooterSeparator, FooterToken, Scope, Type, span,
+};
+
+/// A commit message that is re-parsed as it is edited, like in an editor.
+///
+/// The message is split into its summary line and the body and footers after it.  An edit only
+/// re-parses the [`Region`] it touches, falling back to more of the message when the edit moves
+/// the boundaries between them.  Each region is parsed, and may fail, on its own, so the summary
+/// of a message can be inspected while its footers are still being typed.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{IncrementalParser, Region, Type};
+///
+/// let mut parser = IncrementalParser::new("feat: add thing\n\nRefs: #1");
+/// assert_eq!(parser.type_(), Some(Type::FEAT));
+///
+/// assert_eq!(parser.edit(0..4, "fix"), Region::Summary);
+/// assert_eq!(parser.type_(), Some(Type::FIX));
+/// assert_eq!(parser.description_span(), Some(5..14));
+///
+/// let end = parser.as_str().len();
+/// assert_eq!(parser.edit(end..end, "\nCloses #2"), Region::Footers);
+/// assert_eq!(parser.footers().count(), 2);
+/// assert_eq!(parser.footer_spans()[1].value(), 33..34);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncrementalParser {
+    text: String,
+    /// The end of the summary line, before its line ending
+    summary_end: usize,
+    /// The start of the body or footers, after the summary's line ending
+    trailer_start: usize,
+    summary: Result<Summary, (ErrorKind, usize)>,
+    trailer: Result<Trailer, (ErrorKind, usize)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Summary {
+    ty: Range<usize>,
+    scope: Option<Range<usize>>,
+    exclamation_mark: bool,
+    description: Range<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Trailer {
+    body: Option<Range<usize>>,
+    footers: Vec<FooterSpan>,
+}
+
+/// The part of a message that [`IncrementalParser::edit`] re-parsed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Region {
+    /// Only the summary line.
+    Summary,
+    /// Only the body.
+    Body,
+    /// Only the footers.
+    Footers,
+    /// The body and footers.
+    Trailer,
+    /// The whole message.
+    Message,
+}
+
+/// Where each part of a footer is in an [`IncrementalParser`]'s message.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FooterSpan {
+    token: Range<usize>,
+    separator: FooterSeparator,
+    value: Range<usize>,
+}
+
+impl FooterSpan {
+    /// The byte range of the token.
+    pub fn token(&self) -> Range<usize> {
+        self.token.clone()
+    }
+
+    /// The separator between the token and the value.
+    pub fn separator(&self) -> FooterSeparator {
+        self.separator
+    }
+
+    /// The byte range of the value.
+    pub fn value(&self) -> Range<usize> {
+        self.value.clone()
+    }
+
+    fn shift(&mut self, delta: isize) {
+        shift(&mut self.token, delta);
+        shift(&mut self.value, delta);
+    }
+}
+
+impl IncrementalParser {
+    /// Parse `text` in full.
+    pub fn new(text: impl Into<String>) -> Self {
+        let mut parser = Self {
+            text: text.into(),
+            summary_end: 0,
+            trailer_start: 0,
+            summary: Err((ErrorKind::InvalidFormat, 0)),
+            trailer: Err((ErrorKind::InvalidFormat, 0)),
+        };
+        parser.parse_message();
+        parser
+    }
+
+    /// Replace `range` of the message with `replacement`, returning the region that was
+    /// re-parsed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or not on `char` boundaries, like
+    /// [`String::replace_range`].
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> Region {
+        // Edits splitting `\r\n`, or adding or removing line endings, can move regions
+        let line_endings = self.text[range.clone()].contains(is_line_ending)
+            || replacement.contains(is_line_ending)
+            || self.text[..range.start].ends_with('\r');
+        let delta = replacement.len() as isize - range.len() as isize;
+        let old_len = self.text.len();
+        let footers_start = match &self.trailer {
+            Ok(trailer) => trailer.footers.first().map(|f| f.token.start),
+            Err(_) => None,
+        };
+        let body_start = match &self.trailer {
+            Ok(trailer) => trailer.body.as_ref().map(|b| b.start),
+            Err(_) => None,
+        };
+        self.text.replace_range(range.clone(), replacement);
+
+        if !line_endings && range.end <= self.summary_end {
+            self.summary_end = self.summary_end.wrapping_add_signed(delta);
+            self.trailer_start = self.trailer_start.wrapping_add_signed(delta);
+            self.summary = parse_summary(&self.text[..self.summary_end]);
+            match &mut self.trailer {
+                Ok(trailer) => {
+                    if let Some(body) = &mut trailer.body {
+                        shift(body, delta);
+                    }
+                    for footer in &mut trailer.footers {
+                        footer.shift(delta);
+                    }
+                }
+                Err((_, offset)) => *offset = offset.wrapping_add_signed(delta),
+            }
+            return Region::Summary;
+        }
+
+        if let Some(start) = footers_start.filter(|start| *start <= range.start) {
+            if self.reparse_footers(start) {
+                return Region::Footers;
+            }
+        } else if let Some(start) = body_start
+            .filter(|start| *start < range.start || !line_endings && *start == range.start)
+        {
+            let end = footers_start.unwrap_or(old_len);
+            if range.end <= end && self.reparse_body(start, end.wrapping_add_signed(delta), delta) {
+                return Region::Body;
+            }
+        }
+
+        if self.summary_end < self.trailer_start && self.trailer_start <= range.start {
+            self.trailer = self.parse_trailer();
+            Region::Trailer
+        } else {
+            self.parse_message();
+            Region::Message
+        }
+    }
+
+    /// The full commit message.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Release the commit message.
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Parse the whole commit.
+    pub fn commit(&self) -> Result<Commit<'_>, Error> {
+        Commit::parse(&self.text)
+    }
+
+    /// Why the summary line failed to parse.
+    pub fn summary_error(&self) -> Option<ErrorRef<'_>> {
+        let (kind, offset) = self.summary.as_ref().err()?;
+        Some(ErrorRef::new(*kind, &self.text, *offset))
+    }
+
+    /// Why the body or footers failed to parse.
+    pub fn trailer_error(&self) -> Option<ErrorRef<'_>> {
+        let (kind, offset) = self.trailer.as_ref().err()?;
+        Some(ErrorRef::new(*kind, &self.text, *offset))
+    }
+
+    /// The byte range of the type.
+    pub fn type_span(&self) -> Option<Range<usize>> {
+        Some(self.summary.as_ref().ok()?.ty.clone())
+    }
+
+    /// The type of the commit.
+    pub fn type_(&self) -> Option<Type<'_>> {
+        Some(Type::new_unchecked(&self.text[self.type_span()?]))
+    }
+
+    /// The byte range of the scope, without its parentheses.
+    pub fn scope_span(&self) -> Option<Range<usize>> {
+        self.summary.as_ref().ok()?.scope.clone()
+    }
+
+    /// The optional scope of the commit.
+    pub fn scope(&self) -> Option<Scope<'_>> {
+        Some(Scope::new_unchecked(&self.text[self.scope_span()?]))
+    }
+
+    /// The byte range of the description.
+    pub fn description_span(&self) -> Option<Range<usize>> {
+        Some(self.summary.as_ref().ok()?.description.clone())
+    }
+
+    /// The commit description.
+    pub fn description(&self) -> Option<&str> {
+        Some(&self.text[self.description_span()?])
+    }
+
+    /// The byte range of the body.
+    pub fn body_span(&self) -> Option<Range<usize>> {
+        self.trailer.as_ref().ok()?.body.clone()
+    }
+
+    /// The commit body.
+    pub fn body(&self) -> Option<&str> {
+        Some(&self.text[self.body_span()?])
+    }
+
+    /// Where each footer is.
+    pub fn footer_spans(&self) -> &[FooterSpan] {
+        match &self.trailer {
+            Ok(trailer) => &trailer.footers,
+            Err(_) => &[],
+        }
+    }
+
+    /// The footers of the commit.
+    pub fn footers(&self) -> impl ExactSizeIterator<Item = Footer<'_>> + '_ {
+        self.footer_spans().iter().map(|span| {
+            Footer::new(
+                FooterToken::new_unchecked(&self.text[span.token()]),
+                span.separator(),
+                &self.text[span.value()],
+            )
+        })
+    }
+
+    /// A flag to signal that the commit contains breaking changes, as far as it parsed.
+    pub fn breaking(&self) -> bool {
+        self.summary.as_ref().is_ok_and(|s| s.exclamation_mark)
+            || self.footers().any(|f| NOTES.contains(&f.token().as_str()))
+    }
+
+    fn parse_message(&mut self) {
+        self.summary_end = self.text.find(is_line_ending).unwrap_or(self.text.len());
+        let line_ending = &self.text[self.summary_end..];
+        self.trailer_start = self.summary_end
+            + if line_ending.starts_with("\r\n") {
+                2
+            } else if line_ending.starts_with('\n') {
+                1
+            } else {
+                0
+            };
+        self.summary = parse_summary(&self.text[..self.summary_end]);
+        self.trailer = self.parse_trailer();
+    }
+
+    fn parse_trailer<'s>(&'s self) -> Result<Trailer, (ErrorKind, usize)> {
+        let start = self.trailer_start;
+        if self.summary_end == start && start < self.text.len() {
+            // A lone `\r`
+            return Err((ErrorKind::InvalidFormat, start));
+        }
+        let (body, footers) = (|i: &mut &'s str| trailer::<KindError>(&NOTES, i))
+            .parse(&self.text[start..])
+            .map_err(|err| (err.inner().kind(), start + err.offset()))?;
+        Ok(Trailer {
+            body: body.map(|body| at(&self.text, body)),
+            footers: self.footer_spans_of(footers),
+        })
+    }
+
+    /// Re-parse the footers from `start`, where the first one still begins.
+    fn reparse_footers(&mut self, start: usize) -> bool {
+        let Ok(footers) = parse_footers(&self.text[start..]) else {
+            return false;
+        };
+        let footers = self.footer_spans_of(footers);
+        if let Ok(trailer) = &mut self.trailer {
+            trailer.footers = footers;
+        }
+        true
+    }
+
+    /// Re-parse the body from `start`, expecting it to end where the footers now start.
+    fn reparse_body(&mut self, start: usize, end: usize, delta: isize) -> bool {
+        let mut rest = &self.text[start..];
+        // A body starting with a blank line, or emptied, starts later or not at all
+        if rest.starts_with(is_line_ending) {
+            return false;
+        }
+        let Ok(body) = body::<KindError>(&NOTES, &mut rest) else {
+            return false;
+        };
+        if self.text.len() - rest.len() != end || body.is_empty() {
+            return false;
+        }
+        let body = at(&self.text, body);
+        if let Ok(trailer) = &mut self.trailer {
+            trailer.body = Some(body);
+            for footer in &mut trailer.footers {
+                footer.shift(delta);
+            }
+        }
+        true
+    }
+
+    fn footer_spans_of(&self, footers: Vec<(&str, &str, &str)>) -> Vec<FooterSpan> {
+        footers
+            .into_iter()
+            .map(|(token, separator, value)| FooterSpan {
+                token: at(&self.text, token),
+                separator: match separator {
+                    ":" => FooterSeparator::Value,
+                    _ => FooterSeparator::Ref,
+                },
+                value: at(&self.text, value),
+            })
+            .collect()
+    }
+}
+
+fn parse_summary(line: &str) -> Result<Summary, (ErrorKind, usize)> {
+    let (ty, scope, exclamation_mark, description) = summary::<KindError>
+        .parse(line)
+        .map_err(|err| (err.inner().kind(), err.offset()))?;
+    Ok(Summary {
+        ty: at(line, ty),
+        scope: scope.map(|scope| at(line, scope)),
+        exclamation_mark: exclamation_mark.is_some(),
+        description: at(line, description),
+    })
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_footers<'a>(text: &'a str) -> Result<Vec<(&'a str, &'a str, &'a str)>, ()> {
+    (
+        repeat(0.., |i: &mut &'a str| footer::<KindError>(&NOTES, i)),
+        repeat::<_, _, (), _, _>(0.., line_ending),
+    )
+        .map(|(footers, ())| footers)
+        .parse(text)
+        .map_err(|_| ())
+}
+
+fn is_line_ending(c: char) -> bool {
+    c == '\n' || c == '\r'
+}
+
+fn shift(range: &mut Range<usize>, delta: isize) {
+    range.start = range.start.wrapping_add_signed(delta);
+    range.end = range.end.wrapping_add_signed(delta);
+}
+
+/// The byte range of `part` within `whole`, which `part` must borrow from.
+fn at(whole: &str, part: &str) -> Range<usize> {
+    span(whole, part).expect("parsed parts borrow from the message")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitRef;
+    use alloc::borrow::ToOwned;
+
+    /// Check `parser` against parsing its message from scratch.
+    fn assert_fresh(parser: &IncrementalParser) {
+        let fresh = IncrementalParser::new(parser.as_str());
+        assert_eq!(*parser, fresh, "{:?}", parser.as_str());
+        match CommitRef::parse(parser.as_str()) {
+            Ok(commit) => {
+                assert_eq!(parser.type_(), Some(commit.type_()));
+                assert_eq!(parser.scope(), commit.scope());
+                assert_eq!(parser.description(), Some(commit.description()));
+                assert_eq!(parser.body(), commit.body());
+                assert!(parser.footers().eq(commit.footers()));
+                assert_eq!(parser.breaking(), commit.breaking());
+            }
+            Err(_) => {
+                assert!(parser.summary_error().is_some() || parser.trailer_error().is_some());
+            }
+        }
+    }
+
+    /// Replace the first `needle` with `replacement`.
+    fn replace(parser: &mut IncrementalParser, needle: &str, replacement: &str) -> Region {
+        let start = parser.as_str().find(needle).unwrap();
+        parser.edit(start..start + needle.len(), replacement)
+    }
+
+    #[test]
+    fn test_edits() {
+        let mut parser = IncrementalParser::new(
+            "feat(api): add endpoint\n\nSome body.\n\nMore body.\n\nRefs: #1\nCloses #2\n",
+        );
+        assert_fresh(&parser);
+
+        let edits = [
+            // Summary
+            ("feat", "fix", Region::Summary),
+            ("(api)", "(api)!", Region::Summary),
+            ("(api)", "", Region::Summary),
+            ("add endpoint", "add", Region::Summary),
+            // Body
+            ("Some body", "Any body", Region::Body),
+            ("Any body", "\nAny body", Region::Trailer),
+            ("\n\nAny body", "\nAny body", Region::Trailer),
+            ("More body.", "More body.\nStill body.", Region::Body),
+            ("Still body.", "Still body.\n\nRefs: #0", Region::Trailer),
+            // Footers
+            ("#1", "#3", Region::Footers),
+            ("Closes #2", "Closes #2\n  continued", Region::Footers),
+            (
+                "Refs: #3",
+                "BREAKING CHANGE: everything\nRefs: #3",
+                Region::Footers,
+            ),
+            ("\n\nRefs: #0", "", Region::Trailer),
+            ("Closes #2", "Closes #2\nnot a footer", Region::Footers),
+            ("\nnot a footer", "", Region::Footers),
+            // Line endings
+            ("add\n", "add ", Region::Message),
+            ("fix", "\nfix", Region::Message),
+            ("\n", "", Region::Message),
+        ];
+        for (needle, replacement, region) in edits {
+            let message = parser.as_str().to_owned();
+            let edited = replace(&mut parser, needle, replacement);
+            assert_fresh(&parser);
+            assert_eq!(edited, region, "{message:?}: {needle:?} => {replacement:?}");
+        }
+    }
+
+    #[test]
+    fn test_emptied_body() {
+        let mut parser = IncrementalParser::new("fix: x\n\nBody\n\n");
+        assert_eq!(parser.edit(8..12, ""), Region::Trailer);
+        assert_eq!(parser.body_span(), None);
+        assert_fresh(&parser);
+    }
+
+    #[test]
+    fn test_random_edits() {
+        const PIECES: [&str; 14] = [
+            "",
+            "x",
+            " ",
+            "\n",
+            "\r\n",
+            "\r",
+            "\n\n",
+            "feat",
+            "(api)",
+            "!",
+            ": ",
+            "Refs: #1",
+            "Closes #2",
+            "BREAKING CHANGE: y",
+        ];
+        // xorshift, so failures can be reproduced
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        };
+
+        for _ in 0..200 {
+            let mut parser =
+                IncrementalParser::new("feat(api): add\n\nSome body.\n\nRefs: #1\nCloses #2\n");
+            for _ in 0..20 {
+                // Half of the edits are whole lines, like deleting a paragraph
+                let mut bounds: Vec<_> = parser
+                    .as_str()
+                    .match_indices('\n')
+                    .map(|(i, _)| i + 1)
+                    .collect();
+                bounds.push(0);
+                if next(2) == 0 {
+                    bounds = (0..=parser.as_str().len()).collect();
+                }
+                let a = bounds[next(bounds.len())];
+                let b = bounds[next(bounds.len())];
+                let range = a.min(b)..a.max(b);
+                let message = parser.as_str().to_owned();
+                parser.edit(range.clone(), PIECES[next(PIECES.len())]);
+                let fresh = IncrementalParser::new(parser.as_str());
+                assert_eq!(parser, fresh, "{message:?}: {range:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut parser = IncrementalParser::new("feat add\n\nRefs: #1");
+        assert_eq!(
+            parser.summary_error().unwrap().kind(),
+            ErrorKind::MissingType
+        );
+        assert_eq!(parser.footers().count(), 1);
+
+        assert_eq!(replace(&mut parser, "feat", "feat:"), Region::Summary);
+        assert!(parser.summary_error().is_none());
+        assert_eq!(parser.description(), Some("add"));
+
+        assert_eq!(
+            replace(&mut parser, "\nRefs", "body\nRefs"),
+            Region::Trailer
+        );
+        let err = parser.trailer_error().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidBody);
+        let offset = err.offset();
+        assert_eq!(parser.edit(0..0, "x"), Region::Summary);
+        assert_eq!(parser.trailer_error().unwrap().offset(), offset + 1);
+        assert_fresh(&parser);
+
+        for message in ["", "feat: x\ry", "feat: x\r\n", "feat: x\n\nnot: a\nfooter"] {
+            assert_fresh(&IncrementalParser::new(message));
+        }
+    }
+}
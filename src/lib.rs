@@ -48,17 +48,76 @@
 //! ```
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![warn(clippy::print_stderr)]
 #![warn(clippy::print_stdout)]
 
+extern crate alloc;
+
+mod batch;
 mod commit;
+mod commit_ref;
+mod complete;
+mod decode;
+mod edit;
 mod error;
+mod fix;
+mod footer;
+mod format;
+mod gitmoji;
+#[cfg(feature = "regex")]
+mod header;
+mod highlight;
+mod incremental;
 mod lines;
+#[cfg(feature = "std")]
+mod log;
+mod options;
 mod parser;
+mod reference;
+mod registry;
+mod report;
+mod scope;
+mod ticket;
+mod typed;
 
+pub use batch::{CommitStore, StoredCommit};
 pub use commit::{Commit, Footer, FooterSeparator, FooterToken, Scope, Type};
-pub use error::{Error, ErrorKind};
+pub use commit_ref::CommitRef;
+pub use complete::{Completer, Completion, CompletionKind};
+pub use decode::{DecodeError, DecodeOptions, Decoded};
+pub use edit::CommitBuf;
+pub use error::{Error, ErrorKind, ErrorRef};
+pub use fix::{Fix, Repair};
+pub use footer::{Identity, IssueRef};
+pub use format::FormatOptions;
+pub use gitmoji::Gitmoji;
+#[cfg(feature = "regex")]
+pub use header::HeaderPattern;
+pub use highlight::{Ansi, Highlighter, SemanticToken, TokenKind};
+pub use incremental::{FooterSpan, IncrementalParser, Region};
+#[cfg(feature = "std")]
+pub use log::{LogEntry, LogReader};
+pub use options::ParseOptions;
+pub use reference::{
+    Reference, ReferenceAction, ReferenceExtractor, ReferenceKind, ReferenceSource,
+};
+pub use registry::{Bump, TypeInfo, TypeRegistry};
+pub use report::{CommitReport, Finding, JUnit, Level, Rule, Sarif};
+pub use scope::ScopeSyntax;
+pub use ticket::TicketPattern;
+pub use typed::{ConventionalType, TypedCommit};
+
+/// The byte range of `part` within `whole`, if `part` borrows from it.
+pub(crate) fn span(whole: &str, part: &str) -> Option<core::ops::Range<usize>> {
+    let start = (part.as_ptr() as usize).checked_sub(whole.as_ptr() as usize)?;
+    (start + part.len() <= whole.len()).then_some(start..start + part.len())
+}
+
+/// Implement [`ConventionalType`] for an enum of commit types.
+#[cfg(feature = "derive")]
+pub use git_conventional_derive::ConventionalType;
 
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
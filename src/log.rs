@@ -0,0 +1,203 @@
+//! Streaming over delimited `git log` output.
+
+use std::io::{self, BufRead};
+
+use crate::{Commit, Error, ParseOptions};
+
+/// Read commits from `git log` output, one record at a time.
+///
+/// Records are expected in the format of [`LogReader::FORMAT`]: the SHA, author, and date, then
+/// the raw message, with fields ending at the field separator and records at the record
+/// separator.  Only one record is held in memory at a time.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::LogReader;
+///
+/// // git log --format='%H%x00%an%x00%aI%x00%B%x1e'
+/// let log = "8a6b2c1\x00Lisa\x002024-03-01T12:00:00+01:00\x00feat: add thing\n\x1e\n\
+///            3f9e0d2\x00Bart\x002024-02-29T09:30:00+01:00\x00Update readme\n\x1e\n";
+///
+/// let entries = LogReader::new(log.as_bytes())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(entries[0].sha(), "8a6b2c1");
+/// assert_eq!(entries[0].author(), "Lisa");
+/// assert_eq!(entries[0].commit().unwrap().description(), "add thing");
+/// assert!(entries[1].commit().is_err());
+/// ```
+#[derive(Debug)]
+pub struct LogReader<R> {
+    reader: R,
+    field_separator: u8,
+    record_separator: u8,
+    buffer: Vec<u8>,
+}
+
+impl<R: BufRead> LogReader<R> {
+    /// The `git log --format` that this reads by default.
+    pub const FORMAT: &'static str = "%H%x00%an%x00%aI%x00%B%x1e";
+
+    /// Read records separated by `0x1E`, with fields separated by `0x00`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            field_separator: b'\0',
+            record_separator: 0x1e,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// End each field, except the message, with `separator`.
+    pub fn field_separator(mut self, separator: u8) -> Self {
+        self.field_separator = separator;
+        self
+    }
+
+    /// End each record with `separator`.
+    pub fn record_separator(mut self, separator: u8) -> Self {
+        self.record_separator = separator;
+        self
+    }
+
+    /// Release the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_entry(&mut self) -> io::Result<Option<LogEntry>> {
+        loop {
+            self.buffer.clear();
+            if self
+                .reader
+                .read_until(self.record_separator, &mut self.buffer)?
+                == 0
+            {
+                return Ok(None);
+            }
+            let mut record = self.buffer.as_slice();
+            if let Some(rest) = record.strip_suffix(&[self.record_separator]) {
+                record = rest;
+            }
+            // `git log` ends each record with a newline, which shows up before the next one
+            let record = record.trim_ascii_start();
+            if record.is_empty() {
+                continue;
+            }
+            return self.split(record).map(Some);
+        }
+    }
+
+    fn split(&self, record: &[u8]) -> io::Result<LogEntry> {
+        let mut fields = record.splitn(4, |b| *b == self.field_separator);
+        let mut field = || {
+            let field = fields.next().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "expected a SHA, author, date, and message per record",
+                )
+            })?;
+            String::from_utf8(field.to_vec())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        };
+        Ok(LogEntry {
+            sha: field()?,
+            author: field()?,
+            date: field()?,
+            message: field()?,
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for LogReader<R> {
+    type Item = io::Result<LogEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_entry().transpose()
+    }
+}
+
+/// A commit read by [`LogReader`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    sha: String,
+    author: String,
+    date: String,
+    message: String,
+}
+
+impl LogEntry {
+    /// The commit's SHA.
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// The commit's author.
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    /// The commit's date, as formatted by `git log`.
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+
+    /// The raw commit message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Parse the commit message.
+    pub fn commit(&self) -> Result<Commit<'_>, Error> {
+        Commit::parse(&self.message)
+    }
+
+    /// Parse the commit message with extensions to the specification.
+    pub fn commit_with(&self, options: &ParseOptions) -> Result<Commit<'_>, Error> {
+        Commit::parse_with(&self.message, options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_separators() {
+        let log = "a|Lisa|2024|fix: x\n\nBody with | pipes\n;\nb|Bart|2023|feat: y;";
+        let entries: Vec<_> = LogReader::new(log.as_bytes())
+            .field_separator(b'|')
+            .record_separator(b';')
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message(), "fix: x\n\nBody with | pipes\n");
+        assert_eq!(
+            entries[0].commit().unwrap().body(),
+            Some("Body with | pipes")
+        );
+        assert_eq!(entries[1].sha(), "b");
+        assert_eq!(entries[1].date(), "2023");
+        assert_eq!(entries[1].commit().unwrap().description(), "y");
+    }
+
+    #[test]
+    fn test_malformed() {
+        let mut reader = LogReader::new(&b"a\x00Lisa\x1eb\x00Bart\x002023\x00fix: x"[..]);
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(reader.next().unwrap().unwrap().sha(), "b");
+        assert!(reader.next().is_none());
+
+        let mut reader = LogReader::new(&b"a\0\xff\0d\0m"[..]);
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(LogReader::new(&b""[..]).next().is_none());
+        assert!(LogReader::new(&b"\n\x1e\n"[..]).next().is_none());
+    }
+}
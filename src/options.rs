@@ -0,0 +1,119 @@
+//! Options for parsing commits that extend the Conventional Commit specification.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::TicketPattern;
+use crate::commit::NOTES;
+
+/// Options for [`Commit::parse_with`][crate::Commit::parse_with].
+///
+/// By default, these follow the Conventional Commit specification, like [`Commit::parse`][crate::Commit::parse].
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Commit, ParseOptions, Type};
+///
+/// let options = ParseOptions::new().gitmoji(true).implied_types(true);
+///
+/// let commit = Commit::parse_with(":bug: fix(parser): handle EOF", &options).unwrap();
+/// assert_eq!(commit.gitmoji().unwrap(), ":bug:");
+/// assert_eq!(commit.type_(), Type::FIX);
+///
+/// let commit = Commit::parse_with("✨ add dark mode", &options).unwrap();
+/// assert_eq!(commit.type_(), Type::FEAT);
+/// assert_eq!(commit.description(), "add dark mode");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    gitmoji: bool,
+    implied_types: bool,
+    ticket: Option<TicketPattern>,
+    note_keywords: Option<Vec<String>>,
+    #[cfg(feature = "regex")]
+    header: Option<crate::HeaderPattern>,
+}
+
+impl ParseOptions {
+    /// Follow the Conventional Commit specification.
+    pub const fn new() -> Self {
+        Self {
+            gitmoji: false,
+            implied_types: false,
+            ticket: None,
+            note_keywords: None,
+            #[cfg(feature = "regex")]
+            header: None,
+        }
+    }
+
+    /// Accept a [gitmoji](https://gitmoji.dev) before the type, like `✨ feat: ...` or
+    /// `:bug: fix: ...`.
+    pub const fn gitmoji(mut self, yes: bool) -> Self {
+        self.gitmoji = yes;
+        self
+    }
+
+    /// Accept a summary without a type, like `✨ add dark mode`, when the gitmoji implies one.
+    ///
+    /// Requires [`ParseOptions::gitmoji`].
+    pub const fn implied_types(mut self, yes: bool) -> Self {
+        self.implied_types = yes;
+        self
+    }
+
+    /// Extract ticket keys like `PROJ-42` from around the header, like `PROJ-42 feat: ...` or
+    /// `feat: PROJ-42 ...`.
+    pub fn ticket(mut self, pattern: TicketPattern) -> Self {
+        self.ticket = Some(pattern);
+        self
+    }
+
+    /// Footer tokens that describe a breaking change, replacing `BREAKING CHANGE` and
+    /// `BREAKING-CHANGE`.
+    ///
+    /// Keywords are case-sensitive and may contain spaces, like `BREAKING CHANGES`.  They may be
+    /// loaded at runtime, like the `noteKeywords` of a conventional-changelog preset.
+    pub fn note_keywords<I>(mut self, keywords: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.note_keywords = Some(keywords.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Match the summary line against a regular expression, rather than `type(scope)!: description`.
+    #[cfg(feature = "regex")]
+    pub fn header(mut self, pattern: crate::HeaderPattern) -> Self {
+        self.header = Some(pattern);
+        self
+    }
+
+    pub(crate) fn is_gitmoji(&self) -> bool {
+        self.gitmoji
+    }
+
+    pub(crate) fn is_implied_types(&self) -> bool {
+        self.implied_types
+    }
+
+    /// The note keywords, only allocating when they are not the default.
+    pub(crate) fn notes(&self) -> Cow<'_, [&str]> {
+        match &self.note_keywords {
+            Some(keywords) => Cow::Owned(keywords.iter().map(String::as_str).collect()),
+            None => Cow::Borrowed(&NOTES),
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    pub(crate) fn header_pattern(&self) -> Option<&crate::HeaderPattern> {
+        self.header.as_ref()
+    }
+
+    pub(crate) fn ticket_pattern(&self) -> Option<&TicketPattern> {
+        self.ticket.as_ref()
+    }
+}
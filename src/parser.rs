@@ -1,15 +1,17 @@
 #![allow(clippy::let_unit_value)] // for clarify and to ensure the right type is selected
 
-use std::str;
+use alloc::vec::Vec;
+use core::str;
 
 use winnow::ascii::line_ending;
 use winnow::combinator::alt;
 use winnow::combinator::repeat;
 use winnow::combinator::trace;
-use winnow::combinator::{cut_err, eof, fail, opt, peek};
+use winnow::combinator::{cut_err, eof, fail, opt};
 use winnow::combinator::{delimited, preceded, terminated};
 use winnow::error::{AddContext, ErrMode, ParserError, StrContext};
 use winnow::prelude::*;
+use winnow::stream::{Accumulate, Stream};
 use winnow::token::{take, take_till, take_while};
 
 type CommitDetails<'a> = (
@@ -23,11 +25,12 @@ type CommitDetails<'a> = (
 
 pub(crate) fn parse<
     'a,
-    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
 >(
+    notes: &[&str],
     i: &mut &'a str,
 ) -> ModalResult<CommitDetails<'a>, E> {
-    message.parse_next(i)
+    message(notes, i)
 }
 
 // <CR>              ::= "0x000D"
@@ -56,7 +59,7 @@ fn is_whitespace(c: char) -> bool {
     c.is_whitespace()
 }
 
-fn whitespace<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug>(
+fn whitespace<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug>(
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
     take_while(0.., is_whitespace).parse_next(i)
@@ -67,8 +70,9 @@ fn whitespace<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + st
 //                    |  <summary>, <newline>*
 pub(crate) fn message<
     'a,
-    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
 >(
+    notes: &[&str],
     i: &mut &'a str,
 ) -> ModalResult<CommitDetails<'a>, E> {
     trace("message", move |i: &mut &'a str| {
@@ -76,6 +80,89 @@ pub(crate) fn message<
             terminated(trace("summary", summary), alt((line_ending, eof))).parse_next(i)?;
         let (type_, scope, breaking, description) = summary;
 
+        let (body, footers) = trailer(notes, i)?;
+
+        Ok((type_, scope, breaking.is_some(), description, body, footers))
+    })
+    .parse_next(i)
+}
+
+// <untyped-message> ::= <whitespace>*, <text>, <trailer>
+pub(crate) fn untyped_message<
+    'a,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
+    notes: &[&str],
+    i: &mut &'a str,
+) -> ModalResult<(&'a str, Trailer<'a>), E> {
+    trace("untyped_message", move |i: &mut &'a str| {
+        let description = terminated(
+            preceded(whitespace, text.context(StrContext::Label(DESCRIPTION))),
+            alt((line_ending, eof)),
+        )
+        .parse_next(i)?;
+
+        let trailer = trailer(notes, i)?;
+
+        Ok((description, trailer))
+    })
+    .parse_next(i)
+}
+
+type Trailer<'a> = (Option<&'a str>, Vec<(&'a str, &'a str, &'a str)>);
+
+// <trailer>         ::= <newline>+, <body>, (<newline>+, <footer>)*
+//                    |  (<newline>+, <footer>)*
+//                    |  <newline>*
+pub(crate) fn trailer<
+    'a,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
+    notes: &[&str],
+    i: &mut &'a str,
+) -> ModalResult<Trailer<'a>, E> {
+    accumulate_trailer(notes, i).map(|(body, footers, _)| (body, footers))
+}
+
+// <message>, with the footers left as text for `footer` to parse on demand
+pub(crate) fn lazy_message<
+    'a,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
+    notes: &[&str],
+    i: &mut &'a str,
+) -> ModalResult<LazyCommitDetails<'a>, E> {
+    trace("lazy_message", move |i: &mut &'a str| {
+        let summary =
+            terminated(trace("summary", summary), alt((line_ending, eof))).parse_next(i)?;
+        let (type_, scope, breaking, description) = summary;
+
+        let (body, (), footers) = accumulate_trailer(notes, i)?;
+
+        Ok((type_, scope, breaking.is_some(), description, body, footers))
+    })
+    .parse_next(i)
+}
+
+type LazyCommitDetails<'a> = (
+    &'a str,
+    Option<&'a str>,
+    bool,
+    &'a str,
+    Option<&'a str>,
+    &'a str,
+);
+
+/// Parse a `<trailer>`, collecting its footers into `C` and returning the text they span.
+fn accumulate_trailer<
+    'a,
+    C: Accumulate<(&'a str, &'a str, &'a str)>,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
+    notes: &[&str],
+    i: &mut &'a str,
+) -> ModalResult<(Option<&'a str>, C, &'a str), E> {
+    trace("trailer", move |i: &mut &'a str| {
         // The body MUST begin one blank line after the description.
         let _ = alt((line_ending, eof))
             .context(StrContext::Label(BODY))
@@ -83,21 +170,56 @@ pub(crate) fn message<
 
         let _extra: () = repeat(0.., line_ending).parse_next(i)?;
 
-        let body = opt(body).parse_next(i)?;
+        let body = opt(|i: &mut &'a str| body(notes, i)).parse_next(i)?;
 
-        let footers = repeat(0.., footer).parse_next(i)?;
+        let (footers, text) = repeat(0.., |i: &mut &'a str| footer(notes, i))
+            .with_taken()
+            .parse_next(i)?;
 
         let _: () = repeat(0.., line_ending).parse_next(i)?;
 
-        Ok((type_, scope, breaking.is_some(), description, body, footers))
+        Ok((body, footers, text))
     })
     .parse_next(i)
 }
 
+// <gitmoji>         ::= ":", <shortcode>, ":", <whitespace>*
+//                    |  <emoji>+, <whitespace>*
+pub(crate) fn gitmoji<
+    'a,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
+    i: &mut &'a str,
+) -> ModalResult<&'a str, E> {
+    trace(
+        "gitmoji",
+        terminated(
+            alt((
+                (
+                    ':',
+                    take_while(1.., |c: char| {
+                        c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+')
+                    }),
+                    ':',
+                )
+                    .take(),
+                take_while(1.., is_emoji),
+            )),
+            whitespace,
+        ),
+    )
+    .parse_next(i)
+}
+
+// <emoji>           ::= <any non-ASCII UTF8-octets except letters, digits, or whitespace>
+fn is_emoji(c: char) -> bool {
+    !c.is_ascii() && !c.is_alphanumeric() && !is_whitespace(c)
+}
+
 // <type>            ::= <any UTF8-octets except newline or parens or ":" or "!:" or whitespace>+
 pub(crate) fn type_<
     'a,
-    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
 >(
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
@@ -116,7 +238,7 @@ pub(crate) const TYPE: &str = "type";
 // <scope>           ::= <any UTF8-octets except newline or parens>+
 pub(crate) fn scope<
     'a,
-    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
 >(
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
@@ -134,7 +256,10 @@ pub(crate) const SCOPE: &str = "scope";
 // <summary>         ::= <type>, "(", <scope>, ")", ["!"], ":", <whitespace>*, <text>
 //                    |  <type>, ["!"], ":", <whitespace>*, <text>
 #[allow(clippy::type_complexity)]
-fn summary<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug>(
+pub(crate) fn summary<
+    'a,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
     i: &mut &'a str,
 ) -> ModalResult<(&'a str, Option<&'a str>, Option<&'a str>, &'a str), E> {
     trace(
@@ -157,13 +282,17 @@ pub(crate) const SUMMARY: &str = "SUMMARY";
 pub(crate) const DESCRIPTION: &str = "description";
 
 // <text>            ::= <any UTF8-octets except newline>*
-fn text<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug>(
+fn text<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug>(
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
     trace("text", take_till(1.., is_line_ending)).parse_next(i)
 }
 
-fn body<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug>(
+pub(crate) fn body<
+    'a,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
+    notes: &[&str],
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
     trace("body", move |i: &mut &'a str| {
@@ -174,25 +303,22 @@ fn body<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt
             return Err(ErrMode::Backtrack(err));
         }
 
+        // Byte offset of the first footer, found in a single pass
         let mut offset = 0;
         let mut prior_is_empty = true;
         for line in crate::lines::LinesWithTerminator::new(i) {
-            if prior_is_empty
-                && peek::<_, _, ErrMode<E>, _>((token, separator))
-                    .parse_peek(line.trim_end())
-                    .is_ok()
-            {
+            if prior_is_empty && is_footer(notes, line) {
                 break;
             }
             prior_is_empty = line.trim().is_empty();
 
-            offset += line.chars().count();
+            offset += line.len();
         }
         if offset == 0 {
             fail::<_, (), _>(i)?;
         }
 
-        take(offset).map(str::trim_end).parse_next(i)
+        Ok(i.next_slice(offset).trim_end())
     })
     .parse_next(i)
 }
@@ -200,12 +326,22 @@ fn body<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt
 pub(crate) const BODY: &str = "body";
 
 // <footer>          ::= <token>, <separator>, <whitespace>*, <value>
-fn footer<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug>(
+pub(crate) fn footer<
+    'a,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
+>(
+    notes: &[&str],
     i: &mut &'a str,
 ) -> ModalResult<(&'a str, &'a str, &'a str), E> {
     trace(
         "footer",
-        (token, separator, whitespace, value).map(|(ft, s, _, fv)| (ft, s, fv)),
+        (
+            |i: &mut &'a str| note(notes, i),
+            separator,
+            whitespace,
+            |i: &mut &'a str| value(notes, i),
+        )
+            .map(|(ft, s, _, fv)| (ft, s, fv)),
     )
     .parse_next(i)
 }
@@ -214,24 +350,38 @@ fn footer<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::f
 //                    |  <type>
 pub(crate) fn token<
     'a,
-    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
 >(
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
     trace("token", alt(("BREAKING CHANGE", type_))).parse_next(i)
 }
 
+// <note>            ::= <note-keyword>
+//                    |  <token>
+fn note<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug>(
+    notes: &[&str],
+    i: &mut &'a str,
+) -> ModalResult<&'a str, E> {
+    trace("note", move |i: &mut &'a str| {
+        let keyword = notes.iter().find(|keyword| i.starts_with(*keyword));
+        match keyword {
+            Some(keyword) => take(keyword.chars().count()).parse_next(i),
+            None => token(i),
+        }
+    })
+    .parse_next(i)
+}
+
 // <separator>       ::= ":" | " #"
-fn separator<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug>(
+fn separator<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug>(
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
     trace("sep", alt((":", " #"))).parse_next(i)
 }
 
-pub(crate) fn value<
-    'a,
-    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug,
->(
+fn value<'a, E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug>(
+    notes: &[&str],
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
     if i.is_empty() {
@@ -243,23 +393,35 @@ pub(crate) fn value<
 
     let mut offset = 0;
     for (i, line) in crate::lines::LinesWithTerminator::new(i).enumerate() {
-        if 0 < i
-            && peek::<_, _, ErrMode<E>, _>((token, separator))
-                .parse_peek(line.trim_end())
-                .is_ok()
-        {
+        if 0 < i && is_footer(notes, line) {
             break;
         }
 
-        offset += line.chars().count();
+        offset += line.len();
     }
 
-    take(offset).map(str::trim_end).parse_next(i)
+    Ok(i.next_slice(offset).trim_end())
+}
+
+/// Whether `line` starts with a footer's `<note>, <separator>`.
+///
+/// This is checked for every line of the body and footer values, so it skips building errors
+/// for the lines that don't.
+fn is_footer(notes: &[&str], line: &str) -> bool {
+    footer_start(notes, line).is_some()
+}
+
+/// The `<note>` and `<separator>` that `line` starts with, if it starts a footer.
+pub(crate) fn footer_start<'a>(notes: &[&str], line: &'a str) -> Option<(&'a str, &'a str)> {
+    (|i: &mut &'a str| note::<()>(notes, i), separator::<()>)
+        .parse_peek(line)
+        .ok()
+        .map(|(_, start)| start)
 }
 
 fn exclamation_mark<
     'a,
-    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + std::fmt::Debug,
+    E: ParserError<&'a str> + AddContext<&'a str, StrContext> + core::fmt::Debug,
 >(
     i: &mut &'a str,
 ) -> ModalResult<&'a str, E> {
@@ -272,6 +434,7 @@ pub(crate) const BREAKER: &str = "exclamation_mark";
 #[allow(clippy::non_ascii_literal)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
 
     use winnow::error::ContextError;
 
@@ -279,7 +442,7 @@ mod tests {
         use super::*;
         #[test]
         fn errors() {
-            let mut p = message::<ContextError>;
+            let mut p = |i: &mut &'static str| message::<ContextError>(&[], i);
 
             let input = "Hello World";
             let err = p.parse(input).unwrap_err();
@@ -413,7 +576,7 @@ mod tests {
 
         #[test]
         fn test_body() {
-            let mut p = body::<ContextError>;
+            let mut p = |i: &mut &'static str| body::<ContextError>(&[], i);
 
             // valid
             assert_eq!(p.parse_peek("foo").unwrap(), ("", "foo"));
@@ -453,7 +616,7 @@ mod tests {
 
         #[test]
         fn test_footer() {
-            let mut p = footer::<ContextError>;
+            let mut p = |i: &mut &'static str| footer::<ContextError>(&[], i);
 
             // valid
             assert_eq!(
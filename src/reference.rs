@@ -0,0 +1,602 @@
+//! Extraction of issue, merge request, and ticket references.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{Commit, FooterSeparator};
+
+/// Footer tokens and keywords that close the referenced issue, matching GitHub and GitLab.
+const CLOSING_KEYWORDS: &[&str] = &[
+    "close",
+    "closes",
+    "closed",
+    "closing",
+    "fix",
+    "fixes",
+    "fixed",
+    "fixing",
+    "resolve",
+    "resolves",
+    "resolved",
+    "resolving",
+    "implement",
+    "implements",
+    "implemented",
+];
+
+impl<'a> Commit<'a> {
+    /// All issue and merge request references in the description, body, and footers.
+    ///
+    /// See [`ReferenceExtractor`] for also finding Jira-style ticket keys.
+    pub fn references(&self) -> Vec<Reference<'a>> {
+        ReferenceExtractor::new().extract(self)
+    }
+}
+
+/// Finds references to issues, merge requests, and tickets within a [`Commit`].
+///
+/// Recognized references:
+/// - `#12` and `owner/repo#12` issues
+/// - `!12` and `group/project!12` merge requests
+/// - `ABC-123` ticket keys, when enabled
+///
+/// A reference closes its target when it directly follows a closing keyword, like `fixes #12`,
+/// or when it is in a footer with a closing token, like `Closes #12`.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Commit, ReferenceAction, ReferenceExtractor, ReferenceKind};
+///
+/// let commit = Commit::parse("fix(api): handle timeouts (ABC-123)\n\nFixes: owner/repo#34, !35").unwrap();
+/// let references = ReferenceExtractor::new().jira_projects(["ABC"]).extract(&commit);
+///
+/// assert_eq!(references[0].kind(), ReferenceKind::Jira);
+/// assert_eq!(references[0].id(), "ABC-123");
+/// assert_eq!(references[0].action(), ReferenceAction::Mentions);
+///
+/// assert_eq!(references[1].kind(), ReferenceKind::Issue);
+/// assert_eq!(references[1].repo(), Some("owner/repo"));
+/// assert_eq!(references[1].number(), Some(34));
+/// assert_eq!(references[1].action(), ReferenceAction::Closes);
+///
+/// assert_eq!(references[2].kind(), ReferenceKind::MergeRequest);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReferenceExtractor {
+    jira: Jira,
+    issue_prefixes: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum Jira {
+    #[default]
+    Disabled,
+    AnyProject,
+    Projects(Vec<String>),
+}
+
+impl ReferenceExtractor {
+    /// Find issue and merge request references.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also find ticket keys for these Jira projects, like `ABC` in `ABC-123`.
+    pub fn jira_projects<I, S>(mut self, projects: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.jira = Jira::Projects(projects.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Also find ticket keys for any Jira project.
+    ///
+    /// This is off by default as it also matches text like `UTF-8` or `SHA-256`.
+    pub fn any_jira_project(mut self) -> Self {
+        self.jira = Jira::AnyProject;
+        self
+    }
+
+    /// Also find issues written with these prefixes, like `gh-` in `gh-12`, ignoring case.
+    ///
+    /// `#` is always recognized.
+    pub fn issue_prefixes<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.issue_prefixes = prefixes
+            .into_iter()
+            .map(Into::into)
+            .filter(|p: &String| !p.is_empty())
+            .collect();
+        self
+    }
+
+    /// Find all references in `commit`, in the order they appear.
+    pub fn extract<'a>(&self, commit: &Commit<'a>) -> Vec<Reference<'a>> {
+        let mut references = Vec::new();
+        self.scan(
+            commit.description(),
+            0,
+            ReferenceSource::Description,
+            None,
+            &mut references,
+        );
+        if let Some(body) = commit.body() {
+            self.scan(body, 0, ReferenceSource::Body, None, &mut references);
+        }
+        for (i, footer) in commit.footers().iter().enumerate() {
+            let source = ReferenceSource::Footer(i);
+            let action = if is_closing_keyword(footer.token().as_str()) {
+                ReferenceAction::Closes
+            } else {
+                ReferenceAction::Mentions
+            };
+
+            let value = footer.value();
+            let mut start = 0;
+            if footer.separator() == FooterSeparator::Ref {
+                // The `#` was consumed as the separator, e.g. `Closes #12`
+                let len = digits(value);
+                if 0 < len && !continues_word(value, len) {
+                    references.push(Reference {
+                        kind: ReferenceKind::Issue,
+                        repo: None,
+                        id: &value[..len],
+                        text: &value[..len],
+                        source,
+                        span: 0..len,
+                        action,
+                    });
+                    start = len;
+                }
+            }
+            self.scan(value, start, source, Some(action), &mut references);
+        }
+        references
+    }
+
+    /// The byte ranges of all references in `text`.
+    ///
+    /// With `after_hash`, `text` follows a `#` that was consumed as a footer separator, so a
+    /// leading number is an issue too.
+    pub(crate) fn spans(&self, text: &str, after_hash: bool) -> Vec<Range<usize>> {
+        let mut spans = Vec::new();
+        let mut start = 0;
+        let len = digits(text);
+        if after_hash && 0 < len && !continues_word(text, len) {
+            spans.push(0..len);
+            start = len;
+        }
+        let mut references = Vec::new();
+        self.scan(text, start, ReferenceSource::Body, None, &mut references);
+        spans.extend(references.into_iter().map(|r| r.span));
+        spans
+    }
+
+    /// Find the references in `text` from `start`, which ends any earlier reference.
+    fn scan<'a>(
+        &self,
+        text: &'a str,
+        start: usize,
+        source: ReferenceSource,
+        action: Option<ReferenceAction>,
+        references: &mut Vec<Reference<'a>>,
+    ) {
+        let bytes = text.as_bytes();
+        let mut i = start;
+        // References don't overlap, so a repo can't start before the previous reference ends
+        let mut previous_end = start;
+        while i < bytes.len() {
+            let found = match bytes[i] {
+                b'#' => numbered(text, previous_end, i, ReferenceKind::Issue),
+                b'!' => numbered(text, previous_end, i, ReferenceKind::MergeRequest),
+                b'A'..=b'Z' if !continues_word_before(text, i) => self
+                    .prefixed_issue(text, i)
+                    .or_else(|| self.jira_key(text, i)),
+                _ => self.prefixed_issue(text, i),
+            };
+            let Some((span, kind, repo, id)) = found else {
+                i += 1;
+                continue;
+            };
+
+            let action = action.unwrap_or_else(|| {
+                if follows_closing_keyword(&text[..span.start]) {
+                    ReferenceAction::Closes
+                } else {
+                    ReferenceAction::Mentions
+                }
+            });
+            i = span.end;
+            previous_end = span.end;
+            references.push(Reference {
+                kind,
+                repo,
+                id,
+                text: &text[span.clone()],
+                source,
+                span,
+                action,
+            });
+        }
+    }
+
+    /// Match a custom issue prefix, like `gh-12`, starting at `start`.
+    #[allow(clippy::type_complexity)]
+    fn prefixed_issue<'a>(
+        &self,
+        text: &'a str,
+        start: usize,
+    ) -> Option<(Range<usize>, ReferenceKind, Option<&'a str>, &'a str)> {
+        let rest = text.get(start..)?;
+        let prefix = self.issue_prefixes.iter().find(|prefix| {
+            rest.get(..prefix.len())
+                .is_some_and(|r| r.eq_ignore_ascii_case(prefix))
+        })?;
+        if continues_word_before(text, start) {
+            return None;
+        }
+
+        let number_start = start + prefix.len();
+        let len = digits(&text[number_start..]);
+        let end = number_start + len;
+        if len == 0 || continues_word(text, end) {
+            return None;
+        }
+
+        Some((
+            start..end,
+            ReferenceKind::Issue,
+            None,
+            &text[number_start..end],
+        ))
+    }
+
+    /// Match `ABC-123` starting at `start`.
+    #[allow(clippy::type_complexity)]
+    fn jira_key<'a>(
+        &self,
+        text: &'a str,
+        start: usize,
+    ) -> Option<(Range<usize>, ReferenceKind, Option<&'a str>, &'a str)> {
+        if self.jira == Jira::Disabled {
+            return None;
+        }
+
+        let project_len = text[start..]
+            .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(text.len() - start);
+        let dash = start + project_len;
+        if project_len < 2 || text.as_bytes().get(dash) != Some(&b'-') {
+            return None;
+        }
+        let len = digits(&text[dash + 1..]);
+        let end = dash + 1 + len;
+        if len == 0 || continues_word(text, end) {
+            return None;
+        }
+
+        let project = &text[start..dash];
+        if let Jira::Projects(projects) = &self.jira {
+            if !projects.iter().any(|p| p == project) {
+                return None;
+            }
+        }
+
+        Some((start..end, ReferenceKind::Jira, None, &text[start..end]))
+    }
+}
+
+/// A reference to an issue, merge request, or ticket.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Reference<'a> {
+    kind: ReferenceKind,
+    repo: Option<&'a str>,
+    id: &'a str,
+    text: &'a str,
+    source: ReferenceSource,
+    span: Range<usize>,
+    action: ReferenceAction,
+}
+
+impl<'a> Reference<'a> {
+    /// What is being referenced.
+    pub fn kind(&self) -> ReferenceKind {
+        self.kind
+    }
+
+    /// The repository, or project, the target belongs to, when not the current one.
+    pub fn repo(&self) -> Option<&'a str> {
+        self.repo
+    }
+
+    /// The identifier of the target, like `12` for `#12` or `ABC-123` for a Jira ticket.
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    /// The number of the target, like `12` for `#12` or `123` for `ABC-123`.
+    pub fn number(&self) -> Option<u64> {
+        let digits = self.id.rsplit('-').next().unwrap_or(self.id);
+        digits.parse().ok()
+    }
+
+    /// The reference as written, like `owner/repo#12`.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// The part of the commit the reference was found in.
+    pub fn source(&self) -> ReferenceSource {
+        self.source
+    }
+
+    /// The byte range of the reference within its [`ReferenceSource`].
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Whether the commit closes or just mentions the target.
+    pub fn action(&self) -> ReferenceAction {
+        self.action
+    }
+}
+
+/// What a [`Reference`] refers to.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ReferenceKind {
+    /// `#12`
+    Issue,
+    /// `!12`
+    MergeRequest,
+    /// `ABC-123`
+    Jira,
+}
+
+/// The part of a [`Commit`] a [`Reference`] was found in.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ReferenceSource {
+    /// [`Commit::description`]
+    Description,
+    /// [`Commit::body`]
+    Body,
+    /// The [`Footer::value`][crate::Footer::value] at this index in [`Commit::footers`]
+    Footer(usize),
+}
+
+impl ReferenceSource {
+    /// The text of this part of `commit`, which [`Reference::span`] indexes into.
+    pub fn text<'a>(&self, commit: &Commit<'a>) -> Option<&'a str> {
+        match self {
+            ReferenceSource::Description => Some(commit.description()),
+            ReferenceSource::Body => commit.body(),
+            ReferenceSource::Footer(i) => commit.footers().get(*i).map(|f| f.value()),
+        }
+    }
+}
+
+/// How a commit relates to the target of a [`Reference`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ReferenceAction {
+    /// The commit resolves the target, e.g. `Closes #12`.
+    Closes,
+    /// The commit is only related to the target, e.g. `Refs #12`.
+    Mentions,
+}
+
+/// Match `#12`, `owner/repo#12`, `!12`, or `group/project!12` around the sigil at `sigil`,
+/// with any repo starting at or after `after`.
+#[allow(clippy::type_complexity)]
+fn numbered(
+    text: &str,
+    after: usize,
+    sigil: usize,
+    kind: ReferenceKind,
+) -> Option<(Range<usize>, ReferenceKind, Option<&str>, &str)> {
+    let number_start = sigil + 1;
+    let len = digits(&text[number_start..]);
+    let end = number_start + len;
+    if len == 0 || continues_word(text, end) {
+        return None;
+    }
+
+    let start = text[after..sigil]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !is_repo_char(*c))
+        .map(|(i, c)| after + i + c.len_utf8())
+        .unwrap_or(after);
+    // Like GitHub and GitLab names, repos start with a letter or digit
+    let repo = text[start..sigil].trim_start_matches(|c: char| !c.is_ascii_alphanumeric());
+    let start = sigil - repo.len();
+    let repo = (!repo.is_empty()).then_some(repo);
+    if repo.is_none() && continues_word_before(text, sigil) {
+        return None;
+    }
+
+    Some((start..end, kind, repo, &text[number_start..end]))
+}
+
+fn is_closing_keyword(word: &str) -> bool {
+    CLOSING_KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(word))
+}
+
+fn follows_closing_keyword(before: &str) -> bool {
+    let before = before.trim_end().trim_end_matches(':');
+    let word = before
+        .rsplit(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or(before);
+    is_closing_keyword(word)
+}
+
+fn is_repo_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')
+}
+
+fn digits(text: &str) -> usize {
+    text.bytes().take_while(u8::is_ascii_digit).count()
+}
+
+fn continues_word(text: &str, end: usize) -> bool {
+    text[end..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn continues_word_before(text: &str, start: usize) -> bool {
+    text[..start]
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    fn summarize<'a>(references: &[Reference<'a>]) -> Vec<(&'a str, ReferenceSource, bool)> {
+        references
+            .iter()
+            .map(|r| {
+                (
+                    r.as_str(),
+                    r.source(),
+                    r.action() == ReferenceAction::Closes,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_references() {
+        let commit = Commit::parse(indoc! {"
+            fix: handle #1 and other!2 (fixes #3)
+
+            Closes owner/repo#4 but not issue#x or a#5b.
+            Based on group/project!6, see UTF-8
+
+            Refs: #7, ABC-8
+            Closes #9, #10
+        "})
+        .unwrap();
+
+        let references = commit.references();
+        assert_eq!(
+            summarize(&references),
+            [
+                ("#1", ReferenceSource::Description, false),
+                ("other!2", ReferenceSource::Description, false),
+                ("#3", ReferenceSource::Description, true),
+                ("owner/repo#4", ReferenceSource::Body, true),
+                ("group/project!6", ReferenceSource::Body, false),
+                ("#7", ReferenceSource::Footer(0), false),
+                ("9", ReferenceSource::Footer(1), true),
+                ("#10", ReferenceSource::Footer(1), true),
+            ]
+        );
+
+        let group = &references[4];
+        assert_eq!(group.kind(), ReferenceKind::MergeRequest);
+        assert_eq!(group.repo(), Some("group/project"));
+        assert_eq!(group.id(), "6");
+        let body = ReferenceSource::Body.text(&commit).unwrap();
+        assert_eq!(&body[group.span()], "group/project!6");
+    }
+
+    #[test]
+    fn test_jira() {
+        let commit = Commit::parse("feat: ABC-1 and XYZ-2 over UTF-8\n\nFixes: ABC-3").unwrap();
+
+        let references = ReferenceExtractor::new()
+            .jira_projects(["ABC"])
+            .extract(&commit);
+        assert_eq!(
+            summarize(&references),
+            [
+                ("ABC-1", ReferenceSource::Description, false),
+                ("ABC-3", ReferenceSource::Footer(0), true),
+            ]
+        );
+        assert_eq!(references[0].number(), Some(1));
+        assert_eq!(references[0].repo(), None);
+
+        let references = ReferenceExtractor::new()
+            .any_jira_project()
+            .extract(&commit);
+        assert_eq!(
+            references.iter().map(|r| r.as_str()).collect::<Vec<_>>(),
+            ["ABC-1", "XYZ-2", "UTF-8", "ABC-3"]
+        );
+    }
+
+    #[test]
+    fn test_issue_prefixes() {
+        let commit = Commit::parse("fix: closes GH-12, see #3 and dogh-4\n\nRefs: gh-5").unwrap();
+
+        let references = ReferenceExtractor::new()
+            .issue_prefixes(["gh-"])
+            .any_jira_project()
+            .extract(&commit);
+        assert_eq!(
+            summarize(&references),
+            [
+                ("GH-12", ReferenceSource::Description, true),
+                ("#3", ReferenceSource::Description, false),
+                ("gh-5", ReferenceSource::Footer(0), false),
+            ]
+        );
+        assert_eq!(references[0].kind(), ReferenceKind::Issue);
+        assert_eq!(references[0].id(), "12");
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        // Repos only span ASCII, so they start after the `ü`
+        let commit = Commit::parse("fix: see ü#12, ü/r#3, and ü-r#4").unwrap();
+        let references = commit.references();
+        let texts: Vec<_> = references.iter().map(|r| r.as_str()).collect();
+        assert_eq!(texts, ["r#3", "r#4"]);
+        assert_eq!(references[0].repo(), Some("r"));
+    }
+
+    #[test]
+    fn test_adjacent() {
+        let commit = Commit::parse(
+            "fix: #1-o/r#3
+
+Closes #2-o/r#4",
+        )
+        .unwrap();
+        let references = commit.references();
+        let spans: Vec<_> = references.iter().map(|r| (r.as_str(), r.span())).collect();
+        assert_eq!(
+            spans,
+            [("#1", 0..2), ("o/r#3", 3..8), ("2", 0..1), ("o/r#4", 2..7)]
+        );
+
+        let extractor = ReferenceExtractor::new();
+        assert_eq!(extractor.spans("2-o/r#4", true), [0..1, 2..7]);
+        assert_eq!(extractor.spans("ü #12 ü-r#3", false), [3..6, 10..13]);
+        assert_eq!(
+            extractor.spans("see ./o/r#5 and .-/#6", false),
+            [6..11, 19..21]
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! Metadata about commit types.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Commit, Type};
+
+/// The [semver] impact of a change.
+///
+/// [semver]: https://semver.org
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Bump {
+    /// No release is needed
+    #[default]
+    None,
+    /// Backwards compatible bug fixes
+    Patch,
+    /// Backwards compatible functionality
+    Minor,
+    /// Incompatible API changes
+    Major,
+}
+
+impl Bump {
+    /// Access `str` representation of `Bump`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Bump::None => "none",
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        }
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Everything known about a commit type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeInfo {
+    name: String,
+    aliases: Vec<String>,
+    description: String,
+    bump: Bump,
+    section: Option<String>,
+    hidden: bool,
+}
+
+impl TypeInfo {
+    /// Describe the type `name`, which does not bump the version nor have a changelog section.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            aliases: Vec::new(),
+            description: String::new(),
+            bump: Bump::None,
+            section: None,
+            hidden: false,
+        }
+    }
+
+    /// Also accept `alias` for this type, e.g. `feature` for `feat`.
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Explain when to use this type.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// The version bump for commits of this type.
+    pub fn with_bump(mut self, bump: Bump) -> Self {
+        self.bump = bump;
+        self
+    }
+
+    /// The changelog section for commits of this type.
+    pub fn with_section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    /// Leave commits of this type out of changelogs.
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// The canonical name of the type.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Other names accepted for the type.
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// When to use this type.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The version bump for commits of this type.
+    pub fn bump(&self) -> Bump {
+        self.bump
+    }
+
+    /// The changelog section for commits of this type.
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// Whether commits of this type are left out of changelogs.
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn matches(&self, ty: &str) -> bool {
+        self.is_named(ty)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| Type::new_unchecked(ty) == alias.as_str())
+    }
+
+    fn is_named(&self, ty: &str) -> bool {
+        Type::new_unchecked(ty) == self.name.as_str()
+    }
+}
+
+/// The commit types a project accepts, and what they mean.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Bump, Commit, TypeRegistry};
+///
+/// let registry = TypeRegistry::conventional();
+///
+/// let feature = registry.get("Feature").unwrap();
+/// assert_eq!(feature.name(), "feat");
+/// assert_eq!(feature.section(), Some("Features"));
+///
+/// let commits = ["fix: handle EOF", "feature(cli): add --color", "docs: typo"];
+/// let bump = commits
+///     .iter()
+///     .map(|message| registry.bump(&Commit::parse(message).unwrap()))
+///     .max();
+/// assert_eq!(bump, Some(Bump::Minor));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeRegistry {
+    types: Vec<TypeInfo>,
+}
+
+impl TypeRegistry {
+    /// A registry without any types.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The types from the [Conventional Commits] and [Angular] conventions.
+    ///
+    /// [Conventional Commits]: https://www.conventionalcommits.org
+    /// [Angular]: https://github.com/angular/angular/blob/main/contributing-docs/commit-message-guidelines.md
+    pub fn conventional() -> Self {
+        let types = vec![
+            TypeInfo::new(Type::FEAT.as_str())
+                .with_alias("feature")
+                .with_description("A new feature")
+                .with_bump(Bump::Minor)
+                .with_section("Features"),
+            TypeInfo::new(Type::FIX.as_str())
+                .with_alias("bugfix")
+                .with_description("A bug fix")
+                .with_bump(Bump::Patch)
+                .with_section("Bug Fixes"),
+            TypeInfo::new(Type::PERF.as_str())
+                .with_description("A code change that improves performance")
+                .with_bump(Bump::Patch)
+                .with_section("Performance Improvements"),
+            TypeInfo::new(Type::REVERT.as_str())
+                .with_description("Reverts a previous commit")
+                .with_bump(Bump::Patch)
+                .with_section("Reverts"),
+            TypeInfo::new(Type::DOCS.as_str())
+                .with_alias("doc")
+                .with_description("Documentation only changes")
+                .with_section("Documentation")
+                .with_hidden(true),
+            TypeInfo::new(Type::STYLE.as_str())
+                .with_description("Changes that do not affect the meaning of the code")
+                .with_section("Styles")
+                .with_hidden(true),
+            TypeInfo::new(Type::REFACTOR.as_str())
+                .with_description("A code change that neither fixes a bug nor adds a feature")
+                .with_section("Code Refactoring")
+                .with_hidden(true),
+            TypeInfo::new(Type::TEST.as_str())
+                .with_alias("tests")
+                .with_description("Adding missing tests or correcting existing tests")
+                .with_section("Tests")
+                .with_hidden(true),
+            TypeInfo::new("build")
+                .with_description("Changes that affect the build system or external dependencies")
+                .with_section("Build System")
+                .with_hidden(true),
+            TypeInfo::new("ci")
+                .with_description("Changes to CI configuration files and scripts")
+                .with_section("Continuous Integration")
+                .with_hidden(true),
+            TypeInfo::new(Type::CHORE.as_str())
+                .with_description("Other changes that don't modify source or test files")
+                .with_section("Miscellaneous Chores")
+                .with_hidden(true),
+        ];
+        Self { types }
+    }
+
+    /// Add a type, replacing any existing type with the same name, ignoring case.
+    ///
+    /// The name of the new type takes precedence over the aliases of other types, while its own
+    /// aliases are dropped when they name or alias other types.
+    pub fn insert(&mut self, mut info: TypeInfo) {
+        let others = || self.types.iter().filter(|t| !t.is_named(&info.name));
+        let conflicts: Vec<_> = info
+            .aliases
+            .iter()
+            .filter(|alias| others().any(|t| t.matches(alias)))
+            .cloned()
+            .collect();
+        info.aliases.retain(|alias| !conflicts.contains(alias));
+        for other in self.types.iter_mut().filter(|t| !t.is_named(&info.name)) {
+            other
+                .aliases
+                .retain(|alias| Type::new_unchecked(alias) != info.name.as_str());
+        }
+
+        match self.types.iter_mut().find(|t| t.is_named(&info.name)) {
+            Some(existing) => *existing = info,
+            None => self.types.push(info),
+        }
+    }
+
+    /// Remove a type by name or alias, ignoring case.
+    pub fn remove(&mut self, ty: &str) -> Option<TypeInfo> {
+        let index = self.types.iter().position(|t| t.matches(ty))?;
+        Some(self.types.remove(index))
+    }
+
+    /// Look up a type by name or alias, ignoring case.
+    pub fn get(&self, ty: &str) -> Option<&TypeInfo> {
+        self.types.iter().find(|t| t.matches(ty))
+    }
+
+    /// Whether `ty` is a known name or alias, ignoring case.
+    pub fn contains(&self, ty: &str) -> bool {
+        self.get(ty).is_some()
+    }
+
+    /// The canonical name for `ty`, resolving aliases.
+    pub fn canonical(&self, ty: Type<'_>) -> Option<Type<'_>> {
+        self.get(&ty).map(|t| Type::new_unchecked(t.name()))
+    }
+
+    /// All types, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &TypeInfo> {
+        self.types.iter()
+    }
+
+    /// The version bump required by `commit`.
+    ///
+    /// Breaking changes are always [`Bump::Major`] while unknown types are [`Bump::None`].
+    pub fn bump(&self, commit: &Commit<'_>) -> Bump {
+        if commit.breaking() {
+            return Bump::Major;
+        }
+        self.get(&commit.type_())
+            .map(TypeInfo::bump)
+            .unwrap_or_default()
+    }
+}
+
+impl<'r> IntoIterator for &'r TypeRegistry {
+    type Item = &'r TypeInfo;
+    type IntoIter = core::slice::Iter<'r, TypeInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.types.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_conventional() {
+        let registry = TypeRegistry::conventional();
+        assert_eq!(registry.get("FEAT").unwrap().name(), "feat");
+        assert_eq!(registry.get("bugfix").unwrap().name(), "fix");
+        assert_eq!(
+            registry.canonical(Type::new_unchecked("Feature")),
+            Some(Type::FEAT)
+        );
+        assert_eq!(registry.get("wip"), None);
+
+        let visible: Vec<_> = registry
+            .iter()
+            .filter(|t| !t.is_hidden())
+            .map(|t| t.name())
+            .collect();
+        assert_eq!(visible, ["feat", "fix", "perf", "revert"]);
+    }
+
+    #[test]
+    fn test_bump() {
+        let registry = TypeRegistry::conventional();
+        let bump = |message| registry.bump(&Commit::parse(message).unwrap());
+        assert_eq!(bump("feat: x"), Bump::Minor);
+        assert_eq!(bump("bugfix: x"), Bump::Patch);
+        assert_eq!(bump("docs: x"), Bump::None);
+        assert_eq!(bump("docs!: x"), Bump::Major);
+        assert_eq!(bump("unknown: x"), Bump::None);
+        assert!(Bump::Patch < Bump::Minor);
+    }
+
+    #[test]
+    fn test_custom() {
+        let mut registry = TypeRegistry::new();
+        registry.insert(
+            TypeInfo::new("ops")
+                .with_alias("infra")
+                .with_bump(Bump::Patch),
+        );
+        registry.insert(TypeInfo::new("OPS").with_description("Operations"));
+        assert_eq!(registry.iter().count(), 1);
+        assert_eq!(registry.get("ops").unwrap().description(), "Operations");
+        assert_eq!(registry.get("infra"), None);
+
+        assert_eq!(registry.remove("ops").unwrap().name(), "OPS");
+        assert!(!registry.contains("ops"));
+    }
+
+    #[test]
+    fn test_alias_conflicts() {
+        let mut registry = TypeRegistry::conventional();
+        let count = registry.iter().count();
+
+        // A name shadows the alias of another type, rather than replacing it
+        registry.insert(TypeInfo::new("Feature").with_description("A large feature"));
+        assert_eq!(registry.iter().count(), count + 1);
+        assert_eq!(registry.get("feat").unwrap().description(), "A new feature");
+        assert!(registry.get("feat").unwrap().aliases().is_empty());
+        assert_eq!(registry.get("feature").unwrap().name(), "Feature");
+
+        // Aliases of other types are kept by them
+        registry.insert(
+            TypeInfo::new("wip")
+                .with_alias("FIX")
+                .with_alias("bugfix")
+                .with_alias("draft"),
+        );
+        assert_eq!(registry.get("wip").unwrap().aliases(), ["draft"]);
+        assert_eq!(registry.get("fix").unwrap().name(), "fix");
+        assert_eq!(registry.get("bugfix").unwrap().name(), "fix");
+    }
+}
@@ -0,0 +1,79 @@
+//! Just enough JSON to write reports without pulling in a serializer.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+pub(crate) enum Json<'a> {
+    Object(Vec<(&'a str, Json<'a>)>),
+    Array(Vec<Json<'a>>),
+    String(&'a str),
+    Number(usize),
+}
+
+impl Json<'_> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        match self {
+            Json::Object(fields) if fields.is_empty() => f.write_str("{}"),
+            Json::Object(fields) => {
+                f.write_str("{\n")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    write_indent(f, indent + 1)?;
+                    write_str(f, key)?;
+                    f.write_str(": ")?;
+                    value.write(f, indent + 1)?;
+                    if i + 1 < fields.len() {
+                        f.write_str(",")?;
+                    }
+                    f.write_str("\n")?;
+                }
+                write_indent(f, indent)?;
+                f.write_str("}")
+            }
+            Json::Array(items) if items.is_empty() => f.write_str("[]"),
+            Json::Array(items) => {
+                f.write_str("[\n")?;
+                for (i, item) in items.iter().enumerate() {
+                    write_indent(f, indent + 1)?;
+                    item.write(f, indent + 1)?;
+                    if i + 1 < items.len() {
+                        f.write_str(",")?;
+                    }
+                    f.write_str("\n")?;
+                }
+                write_indent(f, indent)?;
+                f.write_str("]")
+            }
+            Json::String(s) => write_str(f, s),
+            Json::Number(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+impl fmt::Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+fn write_indent(f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+    for _ in 0..indent {
+        f.write_str("  ")?;
+    }
+    Ok(())
+}
+
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
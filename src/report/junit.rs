@@ -0,0 +1,194 @@
+use core::fmt;
+
+use super::{CommitReport, Level, line_column};
+
+/// A [JUnit XML] report with one test case per commit.
+///
+/// Commits with [`Level::Error`] findings are reported as failures, carrying each error and the
+/// offending commit message.
+///
+/// [JUnit XML]: https://github.com/testmoapp/junitxml
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{CommitReport, JUnit};
+///
+/// let reports = [
+///     CommitReport::parse("8a6b2c1", "feat: add JUnit output"),
+///     CommitReport::parse("0f3e9d4", "Add JUnit output"),
+/// ];
+/// let xml = JUnit::new(&reports).to_string();
+/// assert!(xml.contains(r#"<testsuite name="git-conventional" tests="2" failures="1""#));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct JUnit<'r, 'a> {
+    reports: &'r [CommitReport<'a>],
+    suite: &'r str,
+}
+
+impl<'r, 'a> JUnit<'r, 'a> {
+    /// Report the results of validating `reports`.
+    pub fn new(reports: &'r [CommitReport<'a>]) -> Self {
+        Self {
+            reports,
+            suite: env!("CARGO_PKG_NAME"),
+        }
+    }
+
+    /// Name the test suite, and class of each test case, something other than `git-conventional`.
+    pub fn suite(mut self, name: &'r str) -> Self {
+        self.suite = name;
+        self
+    }
+}
+
+impl fmt::Display for JUnit<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tests = self.reports.len();
+        let failures = self.reports.iter().filter(|r| !r.passed()).count();
+        let suite = Escape::attr(self.suite);
+
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(f, r#"<testsuites tests="{tests}" failures="{failures}">"#)?;
+        writeln!(
+            f,
+            r#"  <testsuite name="{suite}" tests="{tests}" failures="{failures}" errors="0" skipped="0">"#
+        )?;
+        for report in self.reports {
+            let name = Escape::attr(report.id());
+            let mut errors = report
+                .findings()
+                .iter()
+                .filter(|finding| finding.level() == Level::Error)
+                .peekable();
+            let Some(first) = errors.peek() else {
+                writeln!(f, r#"    <testcase name="{name}" classname="{suite}"/>"#)?;
+                continue;
+            };
+
+            writeln!(f, r#"    <testcase name="{name}" classname="{suite}">"#)?;
+            write!(
+                f,
+                r#"      <failure message="{}" type="{}">"#,
+                Escape::attr(first.message()),
+                Escape::attr(first.rule().id())
+            )?;
+            for finding in errors {
+                write!(f, "{}", Escape::text(finding.message()))?;
+                if let Some(span) = finding.span() {
+                    let (line, column) = line_column(report.message(), span.start);
+                    write!(f, " (line {line}, column {column})")?;
+                }
+                writeln!(f)?;
+            }
+            writeln!(f)?;
+            writeln!(f, "{}</failure>", Escape::text(report.message().trim_end()))?;
+            writeln!(f, "    </testcase>")?;
+        }
+        writeln!(f, "  </testsuite>")?;
+        writeln!(f, "</testsuites>")
+    }
+}
+
+/// Escape a `str` for use in XML.
+struct Escape<'s> {
+    s: &'s str,
+    attr: bool,
+}
+
+impl<'s> Escape<'s> {
+    fn attr(s: &'s str) -> Self {
+        Self { s, attr: true }
+    }
+
+    fn text(s: &'s str) -> Self {
+        Self { s, attr: false }
+    }
+}
+
+impl fmt::Display for Escape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.s.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' if self.attr => f.write_str("&quot;")?,
+                '\n' if self.attr => f.write_str("&#10;")?,
+                '\r' if self.attr => f.write_str("&#13;")?,
+                '\t' if self.attr => f.write_str("&#9;")?,
+                '\n' | '\r' | '\t' => write!(f, "{c}")?,
+                // Not representable in XML 1.0
+                c if c.is_control() => f.write_str("\u{FFFD}")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::report::Finding;
+    use alloc::string::ToString;
+    use indoc::indoc;
+
+    #[test]
+    fn test_empty() {
+        let xml = JUnit::new(&[]).to_string();
+        assert_eq!(
+            xml,
+            indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites tests="0" failures="0">
+                  <testsuite name="git-conventional" tests="0" failures="0" errors="0" skipped="0">
+                  </testsuite>
+                </testsuites>
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_failures() {
+        let mut lint = CommitReport::parse("0f3e9d4", "feat: <b>bold</b>");
+        lint.push(Finding::lint("no-html", "avoid \"html\"").with_span(6..9));
+        let reports = [
+            CommitReport::parse("8a6b2c1", "fix bad\n\nsome & more\n"),
+            lint,
+        ];
+
+        let xml = JUnit::new(&reports).suite("commits").to_string();
+        assert_eq!(
+            xml,
+            indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites tests="2" failures="1">
+                  <testsuite name="commits" tests="2" failures="1" errors="0" skipped="0">
+                    <testcase name="8a6b2c1" classname="commits">
+                      <failure message="Missing type in the commit summary, expected `type: description`" type="missing-type">Missing type in the commit summary, expected `type: description` (line 1, column 4)
+
+                fix bad
+
+                some &amp; more</failure>
+                    </testcase>
+                    <testcase name="0f3e9d4" classname="commits"/>
+                  </testsuite>
+                </testsuites>
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            Escape::attr("a \"b\" <c>\n&").to_string(),
+            "a &quot;b&quot; &lt;c&gt;&#10;&amp;"
+        );
+        assert_eq!(
+            Escape::text("a \"b\"\n\u{1b}").to_string(),
+            "a \"b\"\n\u{FFFD}"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Reporting of commit validation results for consumption by other tools.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{Commit, Error, ErrorKind};
+
+mod json;
+mod junit;
+mod sarif;
+
+pub use junit::JUnit;
+pub use sarif::Sarif;
+
+/// The validation results for a single commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitReport<'a> {
+    id: &'a str,
+    message: &'a str,
+    findings: Vec<Finding<'a>>,
+}
+
+impl<'a> CommitReport<'a> {
+    /// Start a report, without any findings, for the commit identified by `id` (e.g. its SHA).
+    pub fn new(id: &'a str, message: &'a str) -> Self {
+        Self {
+            id,
+            message,
+            findings: Vec::new(),
+        }
+    }
+
+    /// Validate `message` with [`Commit::parse`], recording any error as a finding.
+    pub fn parse(id: &'a str, message: &'a str) -> Self {
+        let mut report = Self::new(id, message);
+        if let Err(err) = Commit::parse(message) {
+            report.push(Finding::from_error(&err));
+        }
+        report
+    }
+
+    /// Record an additional finding, e.g. from a custom lint.
+    pub fn push(&mut self, finding: Finding<'a>) {
+        self.findings.push(finding);
+    }
+
+    /// The identifier of the commit, usually its SHA.
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    /// The commit message that was validated.
+    pub fn message(&self) -> &'a str {
+        self.message
+    }
+
+    /// All findings for this commit.
+    pub fn findings(&self) -> &[Finding<'a>] {
+        &self.findings
+    }
+
+    /// Whether the commit has no [`Level::Error`] findings.
+    pub fn passed(&self) -> bool {
+        self.findings.iter().all(|f| f.level() != Level::Error)
+    }
+}
+
+/// A single problem found in a commit message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding<'a> {
+    rule: Rule<'a>,
+    level: Level,
+    message: String,
+    span: Option<Range<usize>>,
+}
+
+impl<'a> Finding<'a> {
+    /// A finding for a commit that failed to parse.
+    pub fn from_error(err: &Error) -> Self {
+        Self {
+            rule: Rule::Error(err.kind()),
+            level: Level::Error,
+            message: err.to_string(),
+            span: err.offset().map(|offset| offset..offset),
+        }
+    }
+
+    /// A finding from a custom lint, identified by `id`.
+    ///
+    /// Defaults to [`Level::Warning`].
+    pub fn lint(id: &'a str, message: impl Into<String>) -> Self {
+        Self {
+            rule: Rule::Lint(id),
+            level: Level::Warning,
+            message: message.into(),
+            span: None,
+        }
+    }
+
+    /// Override the severity of the finding.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Point the finding at a byte range within the commit message.
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// The rule that produced the finding.
+    pub fn rule(&self) -> Rule<'a> {
+        self.rule
+    }
+
+    /// The severity of the finding.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// A human-readable explanation of the finding.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The byte range within the commit message the finding applies to, if known.
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+}
+
+/// The rule a [`Finding`] was reported for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Rule<'a> {
+    /// The commit does not conform to the Conventional Commit specification.
+    Error(ErrorKind),
+    /// A custom lint, identified by its ID.
+    Lint(&'a str),
+}
+
+impl<'a> Rule<'a> {
+    /// A stable identifier for the rule.
+    pub fn id(&self) -> &'a str {
+        match self {
+            Rule::Error(kind) => kind.id(),
+            Rule::Lint(id) => id,
+        }
+    }
+}
+
+/// The severity of a [`Finding`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Level {
+    /// The commit is rejected.
+    Error,
+    /// The commit is accepted but should be improved.
+    Warning,
+    /// Informational only.
+    Note,
+}
+
+impl Level {
+    /// Access `str` representation of `Level`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Note => "note",
+        }
+    }
+}
+
+/// All error kinds, in the order they are reported as rules.
+const ERROR_KINDS: [ErrorKind; 7] = [
+    ErrorKind::MissingType,
+    ErrorKind::InvalidScope,
+    ErrorKind::MissingDescription,
+    ErrorKind::InvalidBody,
+    ErrorKind::InvalidFooter,
+    ErrorKind::InvalidFormat,
+    ErrorKind::UnknownType,
+];
+
+/// Convert a byte offset into a 1-based line and (`char`) column.
+fn line_column(message: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(message.len());
+    let before = message.get(..offset).unwrap_or(message);
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_records_error() {
+        let report = CommitReport::parse("abc123", "feat: ok");
+        assert!(report.passed());
+        assert_eq!(report.findings(), []);
+
+        let report = CommitReport::parse("abc123", "Hello World");
+        assert!(!report.passed());
+        let finding = &report.findings()[0];
+        assert_eq!(finding.rule(), Rule::Error(ErrorKind::MissingType));
+        assert_eq!(finding.rule().id(), "missing-type");
+        assert_eq!(finding.level(), Level::Error);
+    }
+
+    #[test]
+    fn test_lint_does_not_fail() {
+        let mut report = CommitReport::parse("abc123", "feat: ok");
+        report.push(Finding::lint("subject-length", "too long"));
+        assert!(report.passed());
+
+        report.push(Finding::lint("no-wip", "WIP commit").with_level(Level::Error));
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn test_line_column() {
+        let message = "feat: ok\n\nbödy\n";
+        assert_eq!(line_column(message, 0), (1, 1));
+        assert_eq!(line_column(message, 6), (1, 7));
+        assert_eq!(line_column(message, 9), (2, 1));
+        assert_eq!(line_column(message, 13), (3, 3));
+        assert_eq!(line_column(message, 100), (4, 1));
+    }
+}
@@ -0,0 +1,268 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::json::Json;
+use super::{CommitReport, ERROR_KINDS, Rule, line_column};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/crate-ci/git-conventional";
+
+/// A [SARIF 2.1.0] log of commit validation results.
+///
+/// Every [`ErrorKind`][crate::ErrorKind] is always listed as a rule, so rule IDs and indices stay
+/// stable across runs; custom lints follow in the order they are first reported.
+///
+/// Each commit is reported as an artifact whose URI is the commit ID, with regions pointing into
+/// the commit message.
+///
+/// [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{CommitReport, Sarif};
+///
+/// let reports = [
+///     CommitReport::parse("8a6b2c1", "feat: add SARIF output"),
+///     CommitReport::parse("0f3e9d4", "Add SARIF output"),
+/// ];
+/// let log = Sarif::new(&reports).to_string();
+/// assert!(log.contains(r#""ruleId": "missing-type""#));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Sarif<'r, 'a> {
+    reports: &'r [CommitReport<'a>],
+    tool_name: &'r str,
+    tool_version: &'r str,
+}
+
+impl<'r, 'a> Sarif<'r, 'a> {
+    /// Report the results of validating `reports`.
+    pub fn new(reports: &'r [CommitReport<'a>]) -> Self {
+        Self {
+            reports,
+            tool_name: env!("CARGO_PKG_NAME"),
+            tool_version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+
+    /// Attribute the results to a different tool than `git-conventional`.
+    pub fn tool(mut self, name: &'r str, version: &'r str) -> Self {
+        self.tool_name = name;
+        self.tool_version = version;
+        self
+    }
+
+    fn rules(&self) -> Vec<Rule<'a>> {
+        let mut rules: Vec<_> = ERROR_KINDS.iter().copied().map(Rule::Error).collect();
+        for finding in self.reports.iter().flat_map(|r| r.findings()) {
+            if !rules.contains(&finding.rule()) {
+                rules.push(finding.rule());
+            }
+        }
+        rules
+    }
+
+    fn to_json(self) -> Json<'r> {
+        let rules = self.rules();
+
+        let mut results = Vec::new();
+        for report in self.reports {
+            for finding in report.findings() {
+                let rule_index = rules
+                    .iter()
+                    .position(|r| *r == finding.rule())
+                    .expect("all rules collected");
+
+                let mut physical = vec![(
+                    "artifactLocation",
+                    Json::Object(vec![("uri", Json::String(report.id()))]),
+                )];
+                if let Some(span) = finding.span() {
+                    let (start_line, start_column) = line_column(report.message(), span.start);
+                    let (end_line, end_column) = line_column(report.message(), span.end);
+                    physical.push((
+                        "region",
+                        Json::Object(vec![
+                            ("startLine", Json::Number(start_line)),
+                            ("startColumn", Json::Number(start_column)),
+                            ("endLine", Json::Number(end_line)),
+                            ("endColumn", Json::Number(end_column)),
+                        ]),
+                    ));
+                }
+
+                results.push(Json::Object(vec![
+                    ("ruleId", Json::String(finding.rule().id())),
+                    ("ruleIndex", Json::Number(rule_index)),
+                    ("level", Json::String(finding.level().as_str())),
+                    (
+                        "message",
+                        Json::Object(vec![("text", Json::String(finding.message()))]),
+                    ),
+                    (
+                        "locations",
+                        Json::Array(vec![Json::Object(vec![
+                            ("physicalLocation", Json::Object(physical)),
+                            (
+                                "logicalLocations",
+                                Json::Array(vec![Json::Object(vec![
+                                    ("name", Json::String(report.id())),
+                                    ("kind", Json::String("commit")),
+                                ])]),
+                            ),
+                        ])]),
+                    ),
+                ]));
+            }
+        }
+
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let mut fields = vec![("id", Json::String(rule.id()))];
+                if let Rule::Error(kind) = rule {
+                    fields.push((
+                        "shortDescription",
+                        Json::Object(vec![("text", Json::String(error_description(*kind)))]),
+                    ));
+                }
+                Json::Object(fields)
+            })
+            .collect();
+
+        Json::Object(vec![
+            ("$schema", Json::String(SCHEMA)),
+            ("version", Json::String("2.1.0")),
+            (
+                "runs",
+                Json::Array(vec![Json::Object(vec![
+                    (
+                        "tool",
+                        Json::Object(vec![(
+                            "driver",
+                            Json::Object(vec![
+                                ("name", Json::String(self.tool_name)),
+                                ("version", Json::String(self.tool_version)),
+                                ("informationUri", Json::String(INFORMATION_URI)),
+                                ("rules", Json::Array(rules)),
+                            ]),
+                        )]),
+                    ),
+                    ("columnKind", Json::String("unicodeCodePoints")),
+                    ("results", Json::Array(results)),
+                ])]),
+            ),
+        ])
+    }
+}
+
+impl fmt::Display for Sarif<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_json())
+    }
+}
+
+fn error_description(kind: crate::ErrorKind) -> &'static str {
+    use crate::ErrorKind;
+
+    match kind {
+        ErrorKind::MissingType => "Missing type in the commit summary",
+        ErrorKind::InvalidScope => "Incorrect scope syntax in the commit summary",
+        ErrorKind::MissingDescription => "Missing description in the commit summary",
+        ErrorKind::InvalidBody => "Incorrect body syntax",
+        ErrorKind::InvalidFooter => "Incorrect footer syntax",
+        ErrorKind::InvalidFormat => "Incorrect conventional commit format",
+        ErrorKind::UnknownType => "Commit type is not one of the accepted types",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::report::Finding;
+    use alloc::string::ToString;
+    use indoc::indoc;
+
+    #[test]
+    fn test_empty() {
+        let log = Sarif::new(&[]).tool("committed", "1.0.0").to_string();
+        assert!(log.contains(r#""name": "committed""#));
+        assert!(log.contains(r#""results": []"#));
+        assert!(log.contains(r#""id": "invalid-format""#));
+    }
+
+    #[test]
+    fn test_results() {
+        let mut lint = CommitReport::parse("0f3e9d4", "feat: \"quoted\"");
+        lint.push(Finding::lint("no-quotes", "avoid \"quotes\"").with_span(6..14));
+        let reports = [CommitReport::parse("8a6b2c1", "fix bad"), lint];
+
+        let log = Sarif::new(&reports).tool("committed", "1.0.0").to_string();
+        let expected = indoc! {r#"
+            "results": [
+                    {
+                      "ruleId": "missing-type",
+                      "ruleIndex": 0,
+                      "level": "error",
+                      "message": {
+                        "text": "Missing type in the commit summary, expected `type: description`"
+                      },
+                      "locations": [
+                        {
+                          "physicalLocation": {
+                            "artifactLocation": {
+                              "uri": "8a6b2c1"
+                            },
+                            "region": {
+                              "startLine": 1,
+                              "startColumn": 4,
+                              "endLine": 1,
+                              "endColumn": 4
+                            }
+                          },
+                          "logicalLocations": [
+                            {
+                              "name": "8a6b2c1",
+                              "kind": "commit"
+                            }
+                          ]
+                        }
+                      ]
+                    },
+                    {
+                      "ruleId": "no-quotes",
+                      "ruleIndex": 7,
+                      "level": "warning",
+                      "message": {
+                        "text": "avoid \"quotes\""
+                      },
+                      "locations": [
+                        {
+                          "physicalLocation": {
+                            "artifactLocation": {
+                              "uri": "0f3e9d4"
+                            },
+                            "region": {
+                              "startLine": 1,
+                              "startColumn": 7,
+                              "endLine": 1,
+                              "endColumn": 15
+                            }
+                          },
+                          "logicalLocations": [
+                            {
+                              "name": "0f3e9d4",
+                              "kind": "commit"
+                            }
+                          ]
+                        }
+                      ]
+                    }
+                  ]
+            "#};
+        assert!(log.contains(expected.trim()), "{log}");
+        assert!(log.contains(r#""id": "no-quotes""#));
+    }
+}
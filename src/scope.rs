@@ -0,0 +1,194 @@
+//! Multiple and hierarchical scopes, like `api,cli` or `core/parser`.
+
+use alloc::vec::Vec;
+
+use crate::Scope;
+
+/// How a [`Scope`] is split into segments and paths.
+///
+/// By default, `,` and `|` separate a list of segments while `/` separates the components of each
+/// segment's path, e.g. `api, core/parser` has the segments `api` and `core/parser`, the latter with
+/// the path `core` → `parser`.
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Commit, ScopeSyntax};
+///
+/// let commit = Commit::parse("fix(core::parser+cli): handle EOF").unwrap();
+/// let scope = commit.scope().unwrap();
+///
+/// let syntax = ScopeSyntax::new().list(&['+']).path(&[':']);
+/// assert!(syntax.has_segment(scope, "cli"));
+/// assert!(syntax.starts_with_path(scope, "core"));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScopeSyntax<'s> {
+    list: &'s [char],
+    path: &'s [char],
+}
+
+impl<'s> ScopeSyntax<'s> {
+    /// Split lists on `,` and `|`, and paths on `/`.
+    pub const fn new() -> Self {
+        Self {
+            list: &[',', '|'],
+            path: &['/'],
+        }
+    }
+
+    /// Separate the list of segments with any of these characters.
+    pub const fn list(mut self, separators: &'s [char]) -> Self {
+        self.list = separators;
+        self
+    }
+
+    /// Separate the components of a path with any of these characters.
+    pub const fn path(mut self, separators: &'s [char]) -> Self {
+        self.path = separators;
+        self
+    }
+
+    /// The non-empty, trimmed segments of `scope`.
+    pub fn segments<'a>(&self, scope: Scope<'a>) -> impl Iterator<Item = Scope<'a>> + use<'a, 's> {
+        split(scope.as_str(), self.list)
+    }
+
+    /// The non-empty, trimmed components of a segment's path.
+    pub fn components<'a>(
+        &self,
+        segment: Scope<'a>,
+    ) -> impl Iterator<Item = Scope<'a>> + use<'a, 's> {
+        split(segment.as_str(), self.path)
+    }
+
+    /// Whether any segment of `scope` is `segment`, ignoring case and whitespace around separators.
+    pub fn has_segment(&self, scope: Scope<'_>, segment: &str) -> bool {
+        self.segments(scope).any(|s| {
+            self.components(s)
+                .eq(self.components(Scope::new_unchecked(segment)))
+        })
+    }
+
+    /// Whether the path of any segment of `scope` starts with the components of `path`, ignoring
+    /// case.
+    pub fn starts_with_path(&self, scope: Scope<'_>, path: &str) -> bool {
+        let prefix: Vec<_> = self.components(Scope::new_unchecked(path)).collect();
+        self.segments(scope).any(|s| {
+            let components: Vec<_> = self.components(s).collect();
+            components.starts_with(&prefix)
+        })
+    }
+}
+
+impl Default for ScopeSyntax<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Scope<'a> {
+    /// The segments of a scope like `api,cli`.
+    ///
+    /// See [`ScopeSyntax`] for other separators.
+    pub fn segments(&self) -> impl Iterator<Item = Scope<'a>> {
+        ScopeSyntax::new().segments(*self)
+    }
+
+    /// The components of a hierarchical scope like `core/parser`.
+    ///
+    /// See [`ScopeSyntax`] for other separators.
+    pub fn components(&self) -> impl Iterator<Item = Scope<'a>> {
+        ScopeSyntax::new().components(*self)
+    }
+
+    /// Whether any segment of the scope is `segment`, ignoring case.
+    ///
+    /// See [`ScopeSyntax`] for other separators.
+    ///
+    /// ```rust
+    /// let commit = git_conventional::Commit::parse("feat(api, core/parser): add thing").unwrap();
+    /// let scope = commit.scope().unwrap();
+    /// assert!(scope.has_segment("API"));
+    /// assert!(scope.has_segment("core/parser"));
+    /// assert!(!scope.has_segment("core"));
+    /// ```
+    pub fn has_segment(&self, segment: &str) -> bool {
+        ScopeSyntax::new().has_segment(*self, segment)
+    }
+
+    /// Whether the path of any segment of the scope starts with `path`, ignoring case.
+    ///
+    /// See [`ScopeSyntax`] for other separators.
+    ///
+    /// ```rust
+    /// let commit = git_conventional::Commit::parse("fix(core/parser): handle EOF").unwrap();
+    /// let scope = commit.scope().unwrap();
+    /// assert!(scope.starts_with_path("core"));
+    /// assert!(scope.starts_with_path("core/parser"));
+    /// assert!(!scope.starts_with_path("cor"));
+    /// ```
+    pub fn starts_with_path(&self, path: &str) -> bool {
+        ScopeSyntax::new().starts_with_path(*self, path)
+    }
+}
+
+fn split<'a, 's>(
+    value: &'a str,
+    separators: &'s [char],
+) -> impl Iterator<Item = Scope<'a>> + use<'a, 's> {
+    value
+        .split(separators)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(Scope::new_unchecked)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_segments() {
+        let scope = Scope::new_unchecked("api, cli|core/parser,, ");
+        assert_eq!(
+            scope.segments().collect::<Vec<_>>(),
+            ["api", "cli", "core/parser"]
+        );
+        assert_eq!(
+            Scope::new_unchecked("core / parser")
+                .components()
+                .collect::<Vec<_>>(),
+            ["core", "parser"]
+        );
+        assert_eq!(
+            Scope::new_unchecked("api").segments().collect::<Vec<_>>(),
+            ["api"]
+        );
+    }
+
+    #[test]
+    fn test_matching() {
+        let scope = Scope::new_unchecked("api, Core / Parser");
+        assert!(scope.has_segment("api"));
+        assert!(scope.has_segment("core/parser"));
+        assert!(!scope.has_segment("core"));
+        assert!(!scope.has_segment("parser"));
+        // `str::contains` still searches substrings
+        assert!(scope.contains("Core"));
+        assert!(scope.starts_with_path("api"));
+        assert!(scope.starts_with_path("core"));
+        assert!(scope.starts_with_path("CORE/parser"));
+        assert!(!scope.starts_with_path("parser"));
+        assert!(!scope.starts_with_path("core/parser/lexer"));
+    }
+
+    #[test]
+    fn test_custom_syntax() {
+        let scope = Scope::new_unchecked("a.b;c");
+        let syntax = ScopeSyntax::new().list(&[';']).path(&['.']);
+        assert_eq!(syntax.segments(scope).collect::<Vec<_>>(), ["a.b", "c"]);
+        assert!(syntax.starts_with_path(scope, "a"));
+        assert!(!scope.starts_with_path("a"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Ticket keys around the summary header, like `PROJ-42 feat: ...` or `feat: PROJ-42 ...`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Where to look for ticket keys like `PROJ-42` in the summary, for [`ParseOptions::ticket`].
+///
+/// A key may be wrapped in brackets, like `[PROJ-42]`, or followed by a colon, like `PROJ-42:`.
+///
+/// [`ParseOptions::ticket`]: crate::ParseOptions::ticket
+///
+/// # Example
+///
+/// ```rust
+/// use git_conventional::{Commit, ParseOptions, TicketPattern, Type};
+///
+/// let options = ParseOptions::new().ticket(TicketPattern::new().projects(["PROJ"]));
+///
+/// let commit = Commit::parse_with("PROJ-42 feat(api): add endpoint", &options).unwrap();
+/// assert_eq!(commit.ticket(), Some("PROJ-42"));
+/// assert_eq!(commit.type_(), Type::FEAT);
+///
+/// let commit = Commit::parse_with("fix(api): [PROJ-43] handle timeouts", &options).unwrap();
+/// assert_eq!(commit.ticket(), Some("PROJ-43"));
+/// assert_eq!(commit.description(), "handle timeouts");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TicketPattern {
+    projects: Projects,
+    prefix: bool,
+    suffix: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Projects {
+    Any,
+    Only(Vec<String>),
+}
+
+impl TicketPattern {
+    /// Look for keys both before and after the header, once projects are given.
+    ///
+    /// See [`TicketPattern::projects`] and [`TicketPattern::any_project`].
+    pub fn new() -> Self {
+        Self {
+            projects: Projects::Only(Vec::new()),
+            prefix: true,
+            suffix: true,
+        }
+    }
+
+    /// Accept keys for these projects, like `PROJ` in `PROJ-42`.
+    pub fn projects<I, S>(mut self, projects: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.projects = Projects::Only(projects.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Accept keys for any project.
+    ///
+    /// This is off by default as it also matches text like `UTF-8` or `SHA-256`, which would be
+    /// taken out of the description.
+    pub fn any_project(mut self) -> Self {
+        self.projects = Projects::Any;
+        self
+    }
+
+    /// Look for a key before the type, like `PROJ-42 feat: ...`.
+    pub fn prefix(mut self, yes: bool) -> Self {
+        self.prefix = yes;
+        self
+    }
+
+    /// Look for a key at the start of the description, like `feat: PROJ-42 ...`.
+    pub fn suffix(mut self, yes: bool) -> Self {
+        self.suffix = yes;
+        self
+    }
+
+    pub(crate) fn is_prefix(&self) -> bool {
+        self.prefix
+    }
+
+    pub(crate) fn is_suffix(&self) -> bool {
+        self.suffix
+    }
+
+    /// Split a leading ticket key from `text`, returning the key and the text after it.
+    pub(crate) fn split<'a>(&self, text: &'a str) -> Option<(&'a str, &'a str)> {
+        let (key, rest) = match text.strip_prefix('[') {
+            Some(bracketed) => {
+                let len = key_len(bracketed)?;
+                let rest = bracketed[len..].strip_prefix(']')?;
+                (&bracketed[..len], rest)
+            }
+            None => {
+                let len = key_len(text)?;
+                (&text[..len], &text[len..])
+            }
+        };
+        let rest = rest.strip_prefix(':').unwrap_or(rest);
+        let trimmed =
+            rest.trim_start_matches(|c: char| c.is_whitespace() && c != '\n' && c != '\r');
+        if trimmed.len() == rest.len() || trimmed.is_empty() || trimmed.starts_with(['\n', '\r']) {
+            return None;
+        }
+
+        if let Projects::Only(projects) = &self.projects {
+            let project = &key[..key.find('-')?];
+            if !projects.iter().any(|p| p == project) {
+                return None;
+            }
+        }
+
+        Some((key, trimmed))
+    }
+}
+
+impl Default for TicketPattern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The length of a leading `PROJ-42`.
+fn key_len(text: &str) -> Option<usize> {
+    let project = text
+        .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+        .unwrap_or(text.len());
+    if project < 2 || !text.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let number = text[project..].strip_prefix('-')?;
+    let digits = number.bytes().take_while(u8::is_ascii_digit).count();
+    (0 < digits).then_some(project + 1 + digits)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        assert_eq!(TicketPattern::new().split("PROJ-42 feat: x"), None);
+
+        let pattern = TicketPattern::new().any_project();
+        assert_eq!(
+            pattern.split("PROJ-42 feat: x"),
+            Some(("PROJ-42", "feat: x"))
+        );
+        assert_eq!(
+            pattern.split("[PROJ-42] feat: x"),
+            Some(("PROJ-42", "feat: x"))
+        );
+        assert_eq!(
+            pattern.split("PROJ-42: feat: x"),
+            Some(("PROJ-42", "feat: x"))
+        );
+        assert_eq!(pattern.split("A1_B-7  x"), Some(("A1_B-7", "x")));
+
+        assert_eq!(pattern.split("PROJ-42feat: x"), None);
+        assert_eq!(pattern.split("PROJ-42 "), None);
+        assert_eq!(pattern.split("PROJ-42 \nfeat: x"), None);
+        assert_eq!(pattern.split("PROJ- x"), None);
+        assert_eq!(pattern.split("P-1 x"), None);
+        assert_eq!(pattern.split("proj-1 x"), None);
+        assert_eq!(pattern.split("[PROJ-42 x"), None);
+
+        let pattern = pattern.projects(["ABC"]);
+        assert_eq!(pattern.split("ABC-1 x"), Some(("ABC-1", "x")));
+        assert_eq!(pattern.split("UTF-8 x"), None);
+    }
+}
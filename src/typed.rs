@@ -0,0 +1,219 @@
+//! Commit types from a fixed vocabulary.
+
+use alloc::boxed::Box;
+use alloc::format;
+
+use crate::{Bump, Commit, Error, ErrorKind, ParseOptions, Type, TypeInfo, span};
+
+/// A fixed set of commit types, usually an enum.
+///
+/// With the `derive` feature, this can be implemented with `#[derive(ConventionalType)]`, along
+/// with `FromStr`, `TryFrom<Type>`, and `Display`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "derive")] {
+/// use git_conventional::{Bump, Commit, ConventionalType, ErrorKind};
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, ConventionalType)]
+/// enum MyType {
+///     /// A new feature
+///     #[conventional(alias = "feature", bump = "minor")]
+///     Feat,
+///     /// A bug fix
+///     #[conventional(bump = "patch")]
+///     Fix,
+///     /// Changes to deployments
+///     Ops,
+/// }
+///
+/// let commit = Commit::parse_as::<MyType>("Feature(ui): add dark mode").unwrap();
+/// assert_eq!(*commit.type_(), MyType::Feat);
+/// assert_eq!(commit.type_().bump(), Bump::Minor);
+/// assert_eq!(commit.commit().scope().unwrap(), "ui");
+///
+/// let err = Commit::parse_as::<MyType>("docs: add guide").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::UnknownType);
+///
+/// assert_eq!("ops".parse::<MyType>().unwrap().to_string(), "ops");
+/// # }
+/// ```
+pub trait ConventionalType: Sized {
+    /// The variant named by `ty`, ignoring case and accepting aliases.
+    fn from_type(ty: Type<'_>) -> Option<Self>;
+
+    /// The canonical name of the type.
+    fn name(&self) -> &'static str;
+
+    /// Other names accepted for the type.
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// The version bump for commits of this type.
+    fn bump(&self) -> Bump {
+        Bump::None
+    }
+
+    /// When to use this type.
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    /// Parse a `str` into the variant it names.
+    ///
+    /// Fails with [`ErrorKind::UnknownType`] when `ty` names no variant.
+    fn parse_type(ty: &str) -> Result<Self, Error> {
+        Self::from_type(Type::new_unchecked(ty)).ok_or_else(|| unknown_type(ty))
+    }
+
+    /// Describe the type for a [`TypeRegistry`][crate::TypeRegistry].
+    fn type_info(&self) -> TypeInfo {
+        let mut info = TypeInfo::new(self.name())
+            .with_description(self.description())
+            .with_bump(self.bump());
+        for alias in self.aliases() {
+            info = info.with_alias(*alias);
+        }
+        info
+    }
+}
+
+/// A [`Commit`] whose type is one of `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypedCommit<'a, T> {
+    type_: T,
+    commit: Commit<'a>,
+}
+
+impl<'a, T> TypedCommit<'a, T> {
+    /// The type of the commit.
+    pub fn type_(&self) -> &T {
+        &self.type_
+    }
+
+    /// The untyped commit.
+    pub fn commit(&self) -> &Commit<'a> {
+        &self.commit
+    }
+
+    /// Release the type and untyped commit.
+    pub fn into_parts(self) -> (T, Commit<'a>) {
+        (self.type_, self.commit)
+    }
+}
+
+impl<'a> Commit<'a> {
+    /// Parse a commit whose type must be one of `T`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the commit does not conform to the
+    /// Conventional Commit specification or if its type is not one of `T`,
+    /// with [`ErrorKind::UnknownType`].
+    pub fn parse_as<T: ConventionalType>(string: &'a str) -> Result<TypedCommit<'a, T>, Error> {
+        Self::parse_as_with(string, &ParseOptions::new())
+    }
+
+    /// Parse a commit whose type must be one of `T`, with extensions to the specification.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the commit does not conform to the
+    /// Conventional Commit specification, as extended by `options`, or if its type is not one of
+    /// `T`, with [`ErrorKind::UnknownType`].
+    pub fn parse_as_with<T: ConventionalType>(
+        string: &'a str,
+        options: &ParseOptions,
+    ) -> Result<TypedCommit<'a, T>, Error> {
+        let commit = Commit::parse_with(string, options)?;
+        let Some(type_) = T::from_type(commit.type_()) else {
+            // Types implied by a gitmoji are not part of the message
+            let offset = span(string, commit.type_().as_str())
+                .or_else(|| span(string, commit.gitmoji()?.as_str()))
+                .map(|span| span.start)
+                .unwrap_or(0);
+            return Err(unknown_type(&commit.type_()).set_commit(string, offset));
+        };
+        Ok(TypedCommit { type_, commit })
+    }
+}
+
+fn unknown_type(ty: &str) -> Error {
+    Error::new(ErrorKind::UnknownType).set_context(Box::new(format!("`{ty}`")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    enum Manual {
+        Feat,
+        Fix,
+    }
+
+    impl ConventionalType for Manual {
+        fn from_type(ty: Type<'_>) -> Option<Self> {
+            if ty == "feat" {
+                Some(Self::Feat)
+            } else if ty == "fix" || ty == "bugfix" {
+                Some(Self::Fix)
+            } else {
+                None
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            match self {
+                Self::Feat => "feat",
+                Self::Fix => "fix",
+            }
+        }
+
+        fn aliases(&self) -> &'static [&'static str] {
+            match self {
+                Self::Feat => &[],
+                Self::Fix => &["bugfix"],
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_as() {
+        let commit = Commit::parse_as::<Manual>("BugFix: handle EOF").unwrap();
+        assert_eq!(*commit.type_(), Manual::Fix);
+        assert_eq!(commit.commit().description(), "handle EOF");
+
+        let err = Commit::parse_as::<Manual>("docs: add guide").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownType);
+        assert_eq!(err.offset(), Some(0));
+        assert_eq!(err.to_string(), "Unknown commit type: `docs`");
+
+        let err = Commit::parse_as::<Manual>("add guide").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingType);
+    }
+
+    #[test]
+    fn test_parse_as_with() {
+        let options = ParseOptions::new().gitmoji(true);
+        let commit = Commit::parse_as_with::<Manual>("✨ feat: add thing", &options).unwrap();
+        assert_eq!(*commit.type_(), Manual::Feat);
+
+        let message = "✨ docs: add guide";
+        let err = Commit::parse_as_with::<Manual>(message, &options).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownType);
+        assert_eq!(err.offset(), message.find("docs"));
+    }
+
+    #[test]
+    fn test_type_info() {
+        let info = Manual::Fix.type_info();
+        assert_eq!(info.name(), "fix");
+        assert_eq!(info.aliases(), ["bugfix"]);
+        assert_eq!(info.bump(), Bump::None);
+        assert_eq!(Manual::parse_type("FEAT").unwrap(), Manual::Feat);
+    }
+}